@@ -0,0 +1,86 @@
+//! 📊 เทียบ parser แบบ owned (`Request`) กับแบบยืม (`RequestRef`)
+//! นับจำนวน allocation ต่อ request ด้วย global allocator ที่นับทุกครั้งที่ alloc
+//!
+//! รันด้วย: `cargo bench --bench request_parsing`
+
+use rust_server::http::{Request, RequestRef};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// 🔢 allocator ที่นับจำนวน alloc/realloc แล้วส่งต่อให้ `System`
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const ITERATIONS: usize = 100_000;
+
+/// 📝 request แบบที่ browser ส่งมาจริงๆ: query หลายค่าและ header หลายบรรทัด
+const RAW: &[u8] = b"GET /api/users/42?tag=rust&tag=http&q=zero+copy&page=2 HTTP/1.1\r\n\
+Host: 127.0.0.1:8000\r\n\
+User-Agent: Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0\r\n\
+Accept: text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8\r\n\
+Accept-Language: th,en-US;q=0.7,en;q=0.3\r\n\
+Accept-Encoding: gzip, deflate, br\r\n\
+Connection: keep-alive\r\n\
+Cookie: session=abc123; theme=dark\r\n\
+\r\n";
+
+fn bench(name: &str, parse: impl Fn()) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let started = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        parse();
+    }
+
+    let elapsed = started.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    println!(
+        "{:<28} {:>6.1} allocs/request {:>8.0} ns/request",
+        name,
+        allocations as f64 / ITERATIONS as f64,
+        elapsed.as_nanos() as f64 / ITERATIONS as f64,
+    );
+}
+
+fn main() {
+    bench("Request (owned)", || {
+        let request = Request::try_from(black_box(RAW)).unwrap();
+        black_box(request.headers().get("host"));
+        black_box(request.query_string().and_then(|qs| qs.get("q")));
+    });
+
+    bench("RequestRef (borrowed)", || {
+        let request = RequestRef::try_from(black_box(RAW)).unwrap();
+        black_box(request.header("host"));
+        black_box(request.query());
+    });
+
+    // 🔍 decode query เฉพาะที่ใช้: ค่าที่มี `+`/`%` เท่านั้นที่ allocate
+    bench("RequestRef + query_param", || {
+        let request = RequestRef::try_from(black_box(RAW)).unwrap();
+        black_box(request.header("host"));
+        black_box(request.query_param("q"));
+    });
+}
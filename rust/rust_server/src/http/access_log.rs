@@ -0,0 +1,279 @@
+use crate::http::{HttpStatus, Request};
+use chrono::{DateTime, FixedOffset, Utc};
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 📝 รูปแบบของ access log แต่ละบรรทัด
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Common Log Format: `127.0.0.1 - - [10/Oct/2000:13:55:36 +0700] "GET / HTTP/1.1" 200 2326`
+    #[default]
+    Common,
+    /// Common + `"Referer" "User-Agent"` (แบบ Apache/nginx `combined`)
+    Combined,
+    /// JSON หนึ่ง object ต่อบรรทัด (เหมาะกับส่งเข้าระบบเก็บ log)
+    Json,
+}
+
+/// 📥 ปลายทางของ access log (stdout, ไฟล์ หรือ closure ใดๆ)
+pub trait LogSink: Send + Sync {
+    /// เขียน 1 บรรทัด (ไม่รวม `\n`)
+    fn write_line(&self, line: &str);
+}
+
+impl<F: Fn(&str) + Send + Sync> LogSink for F {
+    fn write_line(&self, line: &str) {
+        self(line)
+    }
+}
+
+struct Stdout;
+
+impl LogSink for Stdout {
+    fn write_line(&self, line: &str) {
+        println!("{}", line);
+    }
+}
+
+struct FileSink(Mutex<File>);
+
+impl LogSink for FileSink {
+    fn write_line(&self, line: &str) {
+        let mut file = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = writeln!(file, "{}", line) {
+            eprintln!("⚠️ Failed to write access log: {}", e);
+        }
+    }
+}
+
+/// 📊 ข้อมูลของ 1 request ที่ถูก log
+#[derive(Debug)]
+pub struct AccessEntry<'a> {
+    pub peer_addr: Option<SocketAddr>,
+    pub time: DateTime<Utc>,          // 🕰️ เวลาที่ได้รับ request
+    pub request: Option<&'a Request>, // ❔ `None` เมื่อ parse request ไม่สำเร็จ
+    pub status: HttpStatus,
+    pub bytes_sent: u64, // 📦 ขนาด body ที่ส่งไป (ไม่รวม headers)
+    pub duration: Duration,
+}
+
+/// 📜 AccessLog: เขียน access log ของทุก request ตาม `LogFormat` ลง `LogSink`
+///
+/// ```
+/// use chrono::FixedOffset;
+/// use rust_server::http::{AccessLog, LogFormat};
+///
+/// // 🇹🇭 เวลาไทย (+07:00) แบบ combined ลง stdout
+/// let log = AccessLog::new(LogFormat::Combined)
+///     .with_timezone(FixedOffset::east_opt(7 * 3600).unwrap());
+///
+/// // 🧪 ส่งไปที่ closure เอง
+/// let log = AccessLog::new(LogFormat::Json).with_sink(|line: &str| eprintln!("{}", line));
+/// ```
+#[derive(Clone)]
+pub struct AccessLog {
+    format: LogFormat,
+    timezone: FixedOffset,
+    sink: Arc<dyn LogSink>,
+}
+
+impl Default for AccessLog {
+    /// Common Log Format, เวลา UTC, เขียนลง stdout
+    fn default() -> Self {
+        Self::new(LogFormat::default())
+    }
+}
+
+impl AccessLog {
+    /// 🏗️ access log รูปแบบ `format` เขียนลง stdout ด้วยเวลา UTC
+    pub fn new(format: LogFormat) -> Self {
+        Self {
+            format,
+            timezone: FixedOffset::east_opt(0).expect("UTC offset is valid"),
+            sink: Arc::new(Stdout),
+        }
+    }
+
+    /// 🌏 timezone ของเวลาใน log
+    pub fn with_timezone(mut self, timezone: FixedOffset) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// 📥 เขียนลง sink อื่นแทน stdout (เช่น closure ที่ส่งต่อไประบบ log)
+    pub fn with_sink(mut self, sink: impl LogSink + 'static) -> Self {
+        self.sink = Arc::new(sink);
+        self
+    }
+
+    /// 📄 เขียนต่อท้ายไฟล์ `path` (สร้างใหม่ถ้ายังไม่มี)
+    pub fn with_file(mut self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.sink = Arc::new(FileSink(Mutex::new(file)));
+        Ok(self)
+    }
+
+    /// ✍️ เขียน log ของ `entry` ลง sink
+    pub fn log(&self, entry: &AccessEntry) {
+        self.sink.write_line(&self.format_entry(entry));
+    }
+
+    /// 📝 แปลง `entry` เป็นบรรทัดตาม `LogFormat`
+    pub fn format_entry(&self, entry: &AccessEntry) -> String {
+        let time = entry.time.with_timezone(&self.timezone);
+        let peer = entry
+            .peer_addr
+            .map_or_else(|| "-".to_string(), |addr| addr.ip().to_string());
+        let request = entry.request;
+        let header = |name: &str| request.and_then(|request| request.headers().get(name));
+
+        match self.format {
+            LogFormat::Common | LogFormat::Combined => {
+                let request_line = request.map_or_else(
+                    || "-".to_string(),
+                    |request| format!("{} {} HTTP/1.1", request.method(), target(request)),
+                );
+                let bytes = match entry.bytes_sent {
+                    0 => "-".to_string(),
+                    bytes => bytes.to_string(),
+                };
+                let mut line = format!(
+                    "{} - - [{}] \"{}\" {} {}",
+                    peer,
+                    time.format("%d/%b/%Y:%H:%M:%S %z"),
+                    escape_clf(&request_line),
+                    entry.status.code(),
+                    bytes
+                );
+                if self.format == LogFormat::Combined {
+                    let _ = write!(
+                        line,
+                        " \"{}\" \"{}\"",
+                        escape_clf(header("Referer").unwrap_or("-")),
+                        escape_clf(header("User-Agent").unwrap_or("-"))
+                    );
+                }
+                line
+            }
+            LogFormat::Json => {
+                let string = |value: Option<&str>| value.map_or("null".to_string(), json_string);
+                format!(
+                    "{{\"time\":{},\"remote_addr\":{},\"method\":{},\"path\":{},\"query\":{},\"status\":{},\"bytes\":{},\"duration_ms\":{:.3},\"referer\":{},\"user_agent\":{}}}",
+                    json_string(&time.to_rfc3339()),
+                    string(entry.peer_addr.map(|_| peer.as_str())),
+                    string(
+                        request
+                            .map(|request| request.method().to_string())
+                            .as_deref()
+                    ),
+                    string(request.map(|request| request.path().as_str())),
+                    string(request.and_then(Request::query)),
+                    entry.status.code(),
+                    entry.bytes_sent,
+                    entry.duration.as_secs_f64() * 1000.0,
+                    string(header("Referer")),
+                    string(header("User-Agent")),
+                )
+            }
+        }
+    }
+}
+
+/// 🎯 request-target แบบที่ client ส่งมา (`/path?query`)
+fn target(request: &Request) -> String {
+    match request.query() {
+        Some(query) => format!("{}?{}", request.path(), query),
+        None => request.path().to_string(),
+    }
+}
+
+/// 🛡️ escape `"`, `\` และ control characters แบบ Apache (กัน log injection)
+fn escape_clf(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\x{:02x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(request: &Request) -> AccessEntry<'_> {
+        AccessEntry {
+            peer_addr: Some("192.168.1.7:52100".parse().unwrap()),
+            time: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+            request: Some(request),
+            status: HttpStatus::Ok,
+            bytes_sent: 1234,
+            duration: Duration::from_micros(1500),
+        }
+    }
+
+    fn request() -> Request {
+        let raw = b"GET /hello?name=petch HTTP/1.1\r\nReferer: http://localhost/\r\nUser-Agent: curl/8.0 \"x\"\r\n\r\n";
+        Request::try_from(&raw[..]).unwrap()
+    }
+
+    #[test]
+    fn formats_combined_in_configured_timezone() {
+        let request = request();
+        let log = AccessLog::new(LogFormat::Combined)
+            .with_timezone(FixedOffset::east_opt(7 * 3600).unwrap());
+
+        assert_eq!(
+            log.format_entry(&entry(&request)),
+            "192.168.1.7 - - [02/Jan/2024:10:04:05 +0700] \"GET /hello?name=petch HTTP/1.1\" 200 1234 \"http://localhost/\" \"curl/8.0 \\\"x\\\"\""
+        );
+    }
+
+    #[test]
+    fn formats_json_and_writes_to_custom_sink() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let captured = Arc::clone(&lines);
+        let log = AccessLog::new(LogFormat::Json)
+            .with_sink(move |line: &str| captured.lock().unwrap().push(line.to_string()));
+
+        let request = request();
+        log.log(&entry(&request));
+
+        assert_eq!(
+            lines.lock().unwrap()[0],
+            "{\"time\":\"2024-01-02T03:04:05+00:00\",\"remote_addr\":\"192.168.1.7\",\"method\":\"GET\",\"path\":\"/hello\",\"query\":\"name=petch\",\"status\":200,\"bytes\":1234,\"duration_ms\":1.500,\"referer\":\"http://localhost/\",\"user_agent\":\"curl/8.0 \\\"x\\\"\"}"
+        );
+    }
+}
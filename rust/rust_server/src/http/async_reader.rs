@@ -0,0 +1,245 @@
+use crate::http::body::{self, ChunkedDecoder};
+use crate::http::{Error, Headers, Limits, Request, Result};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+
+/// 📖 HTTP/1.1 request reader แบบ async (คู่กับ `RequestReader` ของ server แบบ blocking)
+///
+/// อ่าน head ทีละบรรทัดจนเจอบรรทัดว่าง แล้วอ่าน body ตาม `Content-Length`
+/// หรือ `Transfer-Encoding: chunked` ด้วย limits ชุดเดียวกับ `RequestReader`
+/// ข้อมูลที่อ่านเกินมา (request ถัดไปที่ pipeline มา) ค้างอยู่ใน buffer ไม่หายไป
+pub struct AsyncRequestReader<R> {
+    inner: BufReader<R>,
+    limits: Limits,
+    body_timeout: Option<Duration>, // ⏱️ เวลาที่รอข้อมูลแต่ละก้อนของ body
+}
+
+impl<R: AsyncRead + Unpin> AsyncRequestReader<R> {
+    pub fn new(inner: R, limits: Limits) -> Self {
+        Self {
+            inner: BufReader::new(inner),
+            limits,
+            body_timeout: None,
+        }
+    }
+
+    /// ⏱️ ให้ body ได้ `Error::Timeout` เมื่อไม่มีข้อมูลใหม่มาเกินเวลานี้
+    ///
+    /// นับทีละ read ไม่ใช่ทั้ง body: upload ช้าๆ ที่ยังส่งมาเรื่อยๆ ไม่ถูกตัด
+    /// (ต้องอยู่ใน tokio runtime ที่เปิด time driver)
+    pub fn with_body_timeout(mut self, timeout: Duration) -> Self {
+        self.body_timeout = Some(timeout);
+        self
+    }
+
+    /// ✍️ stream ข้างใน (ใช้เขียน response กลับบน connection เดียวกัน)
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// 📝 อ่าน request ถัดไปจาก stream
+    ///
+    /// # Returns
+    /// * `Ok(Some(request))` - อ่านได้ครบทั้ง head และ body
+    /// * `Ok(None)` - stream ปิดก่อนจะเริ่ม request ใหม่
+    /// * `Err` - request ไม่ถูกต้อง, เกิน limits หรือ I/O error
+    pub async fn read_request(&mut self) -> Result<Option<Request>> {
+        let Some(mut request) = self.read_request_head().await? else {
+            return Ok(None);
+        };
+        self.read_request_body(&mut request).await?;
+
+        Ok(Some(request))
+    }
+
+    /// 🔎 อ่านแค่ head ของ request ถัดไป (body ยังว่าง อ่านต่อด้วย `read_request_body`)
+    ///
+    /// แยกออกมาให้ server จับเวลา head กับ body คนละแบบได้
+    pub async fn read_request_head(&mut self) -> Result<Option<Request>> {
+        match self.read_head().await? {
+            Some(head) => Ok(Some(Request::try_from(&head[..])?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 📦 อ่าน body ของ `request` ที่ได้จาก `read_request_head`
+    pub async fn read_request_body(&mut self, request: &mut Request) -> Result<()> {
+        let body = self.read_body(request.headers()).await?;
+        request.set_body(body);
+        Ok(())
+    }
+
+    /// 🔎 อ่านทีละบรรทัดจนถึงบรรทัดว่าง (รวม `\r\n\r\n` ปิดท้าย)
+    async fn read_head(&mut self) -> Result<Option<Vec<u8>>> {
+        let max_line = self.limits.max_line_length as u64;
+        let mut head = Vec::new();
+
+        loop {
+            let start = head.len();
+            // 📏 อ่านไม่เกิน limit + `\n` จะได้ไม่ต้องเก็บบรรทัดยาวๆ ไว้ทั้งบรรทัด
+            let read = (&mut self.inner)
+                .take(max_line + 1)
+                .read_until(b'\n', &mut head)
+                .await?;
+            let line = &head[start..];
+
+            if read == 0 {
+                if head.is_empty() {
+                    return Ok(None);
+                }
+                return Err(Error::InvalidRequest);
+            }
+            if !line.ends_with(b"\n") {
+                if line.len() as u64 > max_line {
+                    return Err(Error::HeaderTooLarge);
+                }
+                return Err(Error::InvalidRequest); // 🔌 ปิดกลางบรรทัด
+            }
+
+            if line == b"\r\n" {
+                // RFC 9112: ควรข้ามบรรทัดว่างที่นำหน้า request line
+                if start == 0 {
+                    head.clear();
+                    continue;
+                }
+                return Ok(Some(head));
+            }
+            if head.len() > self.limits.max_header_size {
+                return Err(Error::HeaderTooLarge);
+            }
+        }
+    }
+
+    async fn read_body(&mut self, headers: &Headers) -> Result<Vec<u8>> {
+        if body::is_chunked(headers)? {
+            return self.read_chunked().await;
+        }
+
+        let Some(length) = body::content_length(headers)? else {
+            return Ok(Vec::new());
+        };
+        if length > self.limits.max_body_size {
+            return Err(Error::PayloadTooLarge);
+        }
+
+        let mut body = vec![0; length];
+        let mut filled = 0;
+        while filled < length {
+            let read = within(self.body_timeout, self.inner.read(&mut body[filled..])).await?;
+            if read == 0 {
+                return Err(Error::InvalidBody(
+                    "body shorter than Content-Length".to_string(),
+                ));
+            }
+            filled += read;
+        }
+        Ok(body)
+    }
+
+    /// 🧩 ใช้ `ChunkedDecoder` ตัวเดียวกับ `body::read_body` (limits และ error ตรงกัน)
+    async fn read_chunked(&mut self) -> Result<Vec<u8>> {
+        let mut decoder = ChunkedDecoder::new(&self.limits);
+        let mut body = Vec::new();
+
+        while !decoder.is_done() {
+            let input = within(self.body_timeout, self.inner.fill_buf()).await?;
+            if input.is_empty() {
+                decoder.finish()?;
+                break;
+            }
+            let used = decoder.decode(input, &mut body)?;
+            self.inner.consume(used);
+        }
+
+        Ok(body)
+    }
+}
+
+/// ⏱️ รอ I/O ไม่เกิน `timeout` (ถ้ามี) เกินแล้วได้ `Error::Timeout`
+async fn within<T>(
+    timeout: Option<Duration>,
+    io: impl Future<Output = std::io::Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => Ok(tokio::time::timeout(timeout, io)
+            .await
+            .map_err(|_| Error::Timeout)??),
+        None => Ok(io.await?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn reads_pipelined_and_chunked_requests() {
+        let raw: &[u8] = b"\r\nPOST /echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5;ext=1\r\npedia\r\n0\r\nX-Trailer: 1\r\n\r\nGET / HTTP/1.1\r\nContent-Length: 2\r\n\r\nhi";
+        let mut reader = AsyncRequestReader::new(raw, Limits::default());
+
+        let (first, second, end) = block_on(async {
+            (
+                reader.read_request().await.unwrap().unwrap(),
+                reader.read_request().await.unwrap().unwrap(),
+                reader.read_request().await.unwrap(),
+            )
+        });
+        assert_eq!(first.path(), "/echo");
+        assert_eq!(first.body(), b"Wikipedia");
+        assert_eq!(second.body(), b"hi");
+        assert!(end.is_none());
+    }
+
+    #[test]
+    fn enforces_limits_and_rejects_truncated_bodies() {
+        let limits = Limits {
+            max_line_length: 16,
+            ..Limits::default()
+        };
+        let read = |raw: &'static [u8], limits: Limits| {
+            block_on(AsyncRequestReader::new(raw, limits).read_request())
+        };
+
+        assert!(matches!(
+            read(b"GET /a-very-long-path HTTP/1.1\r\n\r\n", limits),
+            Err(Error::HeaderTooLarge)
+        ));
+        assert!(matches!(
+            read(
+                b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhi",
+                Limits::default()
+            ),
+            Err(Error::InvalidBody(_))
+        ));
+        assert!(matches!(
+            read(b"GET / HTTP/1.1\r\nHost: x", Limits::default()),
+            Err(Error::InvalidRequest)
+        ));
+        // 🧮 chunk-size ที่ทำให้ขนาดรวม overflow ได้ 413 ไม่ใช่ panic
+        assert!(matches!(
+            read(
+                b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n1\r\na\r\nffffffffffffffff\r\n",
+                Limits::default()
+            ),
+            Err(Error::PayloadTooLarge)
+        ));
+        // 📏 บรรทัด chunk-size ยาวไม่รู้จบถูกตัดที่ `max_line_length`
+        assert!(matches!(
+            read(
+                b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0000000000000000000000000000000000000001\r\na",
+                Limits {
+                    max_line_length: 32,
+                    ..Limits::default()
+                }
+            ),
+            Err(Error::InvalidBody(_))
+        ));
+    }
+}
@@ -0,0 +1,487 @@
+use crate::http::server::finish_response;
+use crate::http::{
+    AccessEntry, AccessLog, AsyncRequestReader, Body, Compression, Error, HttpStatus, Limits,
+    Request, Response, Result, Router, ShutdownHandle,
+};
+use chrono::Utc;
+use std::io::{BufWriter, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+/// ⏱️ เวลาที่รอ request ที่ค้างอยู่ให้เสร็จหลังสั่ง shutdown
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 💤 เวลาที่รอ request ถัดไปบน keep-alive connection
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 📦 เวลาที่รอข้อมูลก้อนถัดไปของ request body
+const DEFAULT_BODY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 🔁 จำนวน request สูงสุดต่อ 1 connection
+const DEFAULT_MAX_REQUESTS_PER_CONNECTION: usize = 100;
+
+/// 📏 ขนาด chunk ที่ส่งจาก blocking thread มาเขียนลง socket
+const STREAM_CHUNK_SIZE: usize = 16 * 1024;
+
+/// 🎯 AsyncHandler: logic ของ application สำหรับ `AsyncServer`
+///
+/// handler ถูกแชร์ระหว่างทุก connection (`&self`) จึงต้อง `Send + Sync`
+/// ส่วน request มาเป็น `Arc` เพื่อให้ future ถือไว้ข้าม `.await` ได้โดยไม่ต้อง copy
+///
+/// closure ที่คืน future ใช้เป็น handler ได้เลย และ `Router` (handler แบบ sync)
+/// ก็ใช้กับ `AsyncServer` ได้เหมือนกัน:
+///
+/// ```no_run
+/// use rust_server::http::{AsyncServer, HttpStatus, Request, Response};
+/// use std::sync::Arc;
+///
+/// # async fn run() -> rust_server::http::Result<()> {
+/// let server = AsyncServer::new("127.0.0.1:8000".to_string(), |request: Arc<Request>| async move {
+///     // ⏳ await I/O อื่นได้โดยไม่บล็อก thread
+///     Response::new(HttpStatus::Ok, Some(format!("hello {}", request.path())))
+/// });
+/// server.run().await
+/// # }
+/// ```
+pub trait AsyncHandler: Send + Sync + 'static {
+    /// 📨 สร้าง response สำหรับ request ที่ parse สำเร็จ
+    fn handle_request(&self, request: Arc<Request>) -> impl Future<Output = Response> + Send;
+
+    /// ⚠️ สร้าง response เมื่อ parse request ไม่สำเร็จ (ค่าเริ่มต้น: status จาก `HttpStatus::from(e)`)
+    fn handle_bad_request(&self, e: &Error) -> Response {
+        eprintln!("⚠️ Failed to parse request: {}", e);
+        Response::new(HttpStatus::from(e), None)
+    }
+}
+
+impl<F, Fut> AsyncHandler for F
+where
+    F: Fn(Arc<Request>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Response> + Send,
+{
+    fn handle_request(&self, request: Arc<Request>) -> impl Future<Output = Response> + Send {
+        self(request)
+    }
+}
+
+impl AsyncHandler for Router {
+    fn handle_request(&self, request: Arc<Request>) -> impl Future<Output = Response> + Send {
+        // 🧷 server ไม่ได้ถือ `Arc` ตัวอื่นไว้ จึงได้ request มาโดยไม่ต้อง copy body
+        let mut request = Arc::unwrap_or_clone(request);
+        std::future::ready(self.handle(&mut request))
+    }
+}
+
+/// ⚡ AsyncServer: HTTP server บน tokio (เปิดด้วย cargo feature `tokio`)
+///
+/// ใช้ `Request`/`Response`/`HttpStatus` ชุดเดียวกับ `Server` และทำขั้นตอนหลัง handler
+/// (compression, 304, Range, HEAD, keep-alive, access log) เหมือนกัน
+/// ต่างกันที่แต่ละ connection เป็น tokio task แทนการจอง worker thread
+/// จึงรับ connection ที่รอ I/O นานๆ ได้จำนวนมากกว่า
+///
+/// middleware, TLS และ WebSocket ยังใช้ได้กับ `Server` เท่านั้น
+pub struct AsyncServer<H: AsyncHandler> {
+    addr: String,
+    handler: Arc<H>,
+    shutdown: ShutdownHandle,
+    shutdown_timeout: Duration,
+    config: ConnectionConfig,
+}
+
+/// ⚙️ ค่าที่ทุก connection ใช้ร่วมกัน
+struct ConnectionConfig {
+    limits: Limits,
+    idle_timeout: Duration,
+    body_timeout: Duration,
+    max_requests: usize,
+    shutdown: ShutdownHandle,
+    compression: Option<Compression>,
+    access_log: Option<AccessLog>,
+}
+
+impl<H: AsyncHandler> AsyncServer<H> {
+    pub fn new(addr: String, handler: H) -> Self {
+        let shutdown = ShutdownHandle::new();
+        Self {
+            addr,
+            handler: Arc::new(handler),
+            shutdown: shutdown.clone(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            config: ConnectionConfig {
+                limits: Limits::default(),
+                idle_timeout: DEFAULT_IDLE_TIMEOUT,
+                body_timeout: DEFAULT_BODY_TIMEOUT,
+                max_requests: DEFAULT_MAX_REQUESTS_PER_CONNECTION,
+                shutdown,
+                compression: None,
+                access_log: Some(AccessLog::default()),
+            },
+        }
+    }
+
+    /// 💤 เวลาที่รอ request ถัดไป (รวมการอ่าน head) ก่อนปิด connection (ค่าเริ่มต้น: 5 วินาที)
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.config.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// 📦 เวลาที่รอข้อมูลก้อนถัดไปของ body เกินแล้วตอบ 408 (ค่าเริ่มต้น: 30 วินาที)
+    pub fn with_body_timeout(mut self, body_timeout: Duration) -> Self {
+        self.config.body_timeout = body_timeout;
+        self
+    }
+
+    /// 🔁 กำหนดจำนวน request สูงสุดต่อ connection (ค่าเริ่มต้น: 100)
+    pub fn with_max_requests_per_connection(mut self, max_requests: usize) -> Self {
+        self.config.max_requests = max_requests;
+        self
+    }
+
+    /// 🛑 handle สำหรับสั่ง shutdown (ใช้ตัวเดียวกับ `Server`)
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// ⏱️ กำหนดเวลาที่รอ request ที่ค้างอยู่หลังสั่ง shutdown (ค่าเริ่มต้น: 30 วินาที)
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// 🚧 กำหนด limits ทั้งหมดของ request (header, line, body)
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
+    /// 📦 กำหนดขนาด request body สูงสุด เกินแล้วตอบ 413
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.config.limits.max_body_size = max_body_size;
+        self
+    }
+
+    /// 🗜️ เปิดการบีบอัด response
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.config.compression = Some(compression);
+        self
+    }
+
+    /// 📜 กำหนด access log (ค่าเริ่มต้น: Common Log Format เวลา UTC ลง stdout)
+    pub fn with_access_log(mut self, access_log: AccessLog) -> Self {
+        self.config.access_log = Some(access_log);
+        self
+    }
+
+    /// 🔇 ปิด access log
+    pub fn without_access_log(mut self) -> Self {
+        self.config.access_log = None;
+        self
+    }
+
+    /// 🚀 bind address แล้วรับ connection จนกว่าจะถูกสั่ง shutdown
+    pub async fn run(self) -> Result<()> {
+        let listener = TcpListener::bind(&self.addr).await?;
+        self.serve(listener).await
+    }
+
+    /// 🎧 รัน server บน listener ที่ bind ไว้แล้ว
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        let local_addr = listener.local_addr()?;
+        self.shutdown.set_local_addr(local_addr);
+        // 🛑 สั่ง shutdown ก่อนมี `local_addr` จะไม่มีใครปลุก `accept()` (เหมือน `Server::serve`)
+        if self.shutdown.is_shutdown() {
+            return Ok(());
+        }
+        println!("🌍 Async server running on {}", local_addr);
+
+        let config = Arc::new(self.config);
+        let mut connections = JoinSet::new();
+
+        loop {
+            let accepted = listener.accept().await;
+            // 🛑 ถูกสั่ง shutdown: หยุดรับ connection ใหม่ (connection ที่ปลุกเราถูกทิ้งไป)
+            if self.shutdown.is_shutdown() {
+                break;
+            }
+            let (stream, peer_addr) = match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    eprintln!("⚠️ Failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+
+            // 🧹 เก็บ task ที่จบแล้ว ไม่ให้ JoinSet โตไปเรื่อยๆ
+            while connections.try_join_next().is_some() {}
+
+            let handler = Arc::clone(&self.handler);
+            let config = Arc::clone(&config);
+            connections.spawn(async move {
+                if let Err(e) = handle_connection(&*handler, stream, peer_addr, &config).await {
+                    eprintln!("⚠️ Connection dropped: {}", e);
+                }
+            });
+        }
+
+        // ⏳ ให้ request ที่ค้างอยู่ทำงานให้เสร็จภายใน deadline
+        println!("🛑 Shutting down, waiting for in-flight requests...");
+        let drain = async { while connections.join_next().await.is_some() {} };
+        if tokio::time::timeout(self.shutdown_timeout, drain)
+            .await
+            .is_err()
+        {
+            eprintln!(
+                "⚠️ Shutdown timeout ({:?}) reached, abandoning in-flight requests",
+                self.shutdown_timeout
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// 🔗 จัดการ connection เดียวแบบเดียวกับ `Server`: อ่าน request, เรียก handler,
+/// ส่ง response วนไปจนกว่า client ขอปิด, ว่างเกิน idle timeout, ครบ max requests
+/// หรือ server ถูกสั่ง shutdown
+async fn handle_connection<H: AsyncHandler>(
+    handler: &H,
+    stream: TcpStream,
+    peer_addr: SocketAddr,
+    config: &ConnectionConfig,
+) -> Result<()> {
+    let mut reader =
+        AsyncRequestReader::new(stream, config.limits).with_body_timeout(config.body_timeout);
+
+    for served in 1.. {
+        // 💤 head ต้องมาถึงภายใน idle timeout (กัน client ที่ส่งทีละ byte)
+        let read = tokio::time::timeout(config.idle_timeout, reader.read_request_head()).await;
+        let mut request = match read {
+            Ok(Ok(Some(request))) => request,
+            Ok(Ok(None)) | Ok(Err(Error::Timeout)) | Err(_) => return Ok(()),
+            Ok(Err(e @ Error::IO(_))) => return Err(e),
+            Ok(Err(e)) => return reject(handler, &mut reader, peer_addr, config, None, &e).await,
+        };
+        // 📦 body จับเวลาทีละ read (upload ช้าๆ ไม่ถูกตัด) เงียบเกิน body timeout ตอบ 408
+        match reader.read_request_body(&mut request).await {
+            Ok(()) => {}
+            Err(e @ Error::IO(_)) => return Err(e),
+            Err(e) => {
+                return reject(handler, &mut reader, peer_addr, config, Some(&request), &e).await;
+            }
+        }
+        let (time, started) = (Utc::now(), Instant::now());
+
+        // 🪶 handler ได้ request ไปคนเดียว server เก็บแค่ส่วนหัวไว้ใช้ต่อ
+        let head = request.head();
+        let mut response = handler.handle_request(Arc::new(request)).await;
+        let request = head;
+        // 🔀 ยก connection ให้ protocol อื่น (WebSocket) ได้เฉพาะ `Server` แบบ blocking
+        if response.take_upgrade().is_some() {
+            response = Response::new(HttpStatus::NotImplemented, None);
+        }
+        let mut response = finish_response(&request, response, config.compression.as_ref());
+
+        // 🔁 ใช้ connection ต่อได้ถ้าทั้งสองฝั่งไม่ขอปิด และยังไม่ถึง limit
+        let keep_alive = request.keep_alive()
+            && !response.closes_connection()
+            && served < config.max_requests
+            && !config.shutdown.is_shutdown();
+        if !keep_alive {
+            response = response.with_header("Connection", "close");
+        }
+
+        let status = *response.status();
+        let bytes_sent = send(response, reader.get_mut()).await?;
+
+        if let Some(access_log) = &config.access_log {
+            access_log.log(&AccessEntry {
+                peer_addr: Some(peer_addr),
+                time,
+                request: Some(&request),
+                status,
+                bytes_sent,
+                duration: started.elapsed(),
+            });
+        }
+
+        if !keep_alive {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// ⚠️ request ไม่ถูกต้องหรือ body มาไม่ทัน: ตอบ 400/408/413/... แล้วปิด connection
+async fn reject<H: AsyncHandler>(
+    handler: &H,
+    reader: &mut AsyncRequestReader<TcpStream>,
+    peer_addr: SocketAddr,
+    config: &ConnectionConfig,
+    request: Option<&Request>,
+    e: &Error,
+) -> Result<()> {
+    let (time, started) = (Utc::now(), Instant::now());
+    let response = handler
+        .handle_bad_request(e)
+        .with_header("Connection", "close");
+    let status = *response.status();
+    let bytes_sent = send(response, reader.get_mut()).await?;
+    if let Some(access_log) = &config.access_log {
+        access_log.log(&AccessEntry {
+            peer_addr: Some(peer_addr),
+            time,
+            request,
+            status,
+            bytes_sent,
+            duration: started.elapsed(),
+        });
+    }
+    Ok(())
+}
+
+/// 📤 เขียน response ลง async stream ด้วย framing เดียวกับ `Response::send`
+///
+/// body ใน memory ประกอบเป็น buffer แล้วเขียนทีเดียว ส่วนไฟล์/stream ที่อ่านแบบ blocking
+/// ให้ blocking thread ของ tokio อ่านแล้วส่งเป็น chunk มาทาง channel (ไม่บล็อก runtime)
+async fn send<W: AsyncWrite + Unpin>(response: Response, stream: &mut W) -> Result<u64> {
+    if matches!(response.body(), Body::Empty | Body::Bytes(_)) {
+        let mut buffer = Vec::new();
+        let sent = response.send(&mut buffer)?;
+        stream.write_all(&buffer).await?;
+        stream.flush().await?;
+        return Ok(sent);
+    }
+
+    let (sender, mut receiver) = mpsc::channel(4);
+    let sending = tokio::task::spawn_blocking(move || {
+        let mut writer = BufWriter::with_capacity(STREAM_CHUNK_SIZE, ChannelWriter(sender));
+        response.send(&mut writer)
+    });
+    // ❌ เขียนไม่ได้ก็เลิกรับ: channel ปิดแล้วฝั่ง blocking จะได้ error และหยุดอ่านเอง
+    while let Some(chunk) = receiver.recv().await {
+        stream.write_all(&chunk).await?;
+    }
+    stream.flush().await?;
+
+    let sent = sending.await.map_err(|e| Error::IO(e.to_string()))??;
+    Ok(sent)
+}
+
+/// 🌉 `Write` ที่ส่งข้อมูลแต่ละก้อนเข้า channel (รอเมื่อ channel เต็ม = backpressure)
+struct ChannelWriter(mpsc::Sender<Vec<u8>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .blocking_send(buf.to_vec())
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn serves_async_handlers_with_keep_alive_and_streamed_bodies() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let server = AsyncServer::new("127.0.0.1:0".to_string(), |request: Arc<Request>| async move {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                match request.path().as_str() {
+                    "/stream" => Response::builder(HttpStatus::Ok)
+                        .stream(&b"streamed"[..], None)
+                        .build(),
+                    _ => Response::new(HttpStatus::Ok, Some(format!("echo {}", request.body().len()))),
+                }
+            })
+            .without_access_log();
+            let shutdown = server.shutdown_handle();
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let running = tokio::spawn(server.serve(listener));
+
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"POST / HTTP/1.1\r\nContent-Length: 3\r\n\r\nabcGET /stream HTTP/1.1\r\nConnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+
+            assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+            assert!(response.contains("\r\n\r\necho 3HTTP/1.1 200 OK\r\n"));
+            assert!(response.contains("Transfer-Encoding: chunked"));
+            assert!(response.ends_with("\r\n\r\n8\r\nstreamed\r\n0\r\n\r\n"));
+
+            // 🛑 ShutdownHandle ปลุก accept ด้วย connection เปล่าจาก thread อื่น
+            tokio::task::spawn_blocking(move || shutdown.shutdown())
+                .await
+                .unwrap();
+            assert!(running.await.unwrap().is_ok());
+        });
+    }
+
+    #[test]
+    fn times_out_stalled_bodies_with_408_but_not_slow_ones() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let server = AsyncServer::new(
+                "127.0.0.1:0".to_string(),
+                |request: Arc<Request>| async move {
+                    Response::new(
+                        HttpStatus::Ok,
+                        Some(format!("echo {}", request.body().len())),
+                    )
+                },
+            )
+            .with_idle_timeout(Duration::from_millis(100))
+            .with_body_timeout(Duration::from_millis(300))
+            .without_access_log();
+            let shutdown = server.shutdown_handle();
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let running = tokio::spawn(server.serve(listener));
+
+            // 🐢 body ที่มาช้ากว่า idle timeout แต่ยังมาเรื่อยๆ ได้ตอบปกติ
+            let mut slow = TcpStream::connect(addr).await.unwrap();
+            slow.write_all(b"POST / HTTP/1.1\r\nContent-Length: 4\r\nConnection: close\r\n\r\nab")
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            slow.write_all(b"cd").await.unwrap();
+            let mut response = String::new();
+            slow.read_to_string(&mut response).await.unwrap();
+            assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+            assert!(response.ends_with("echo 4"));
+
+            // 🧊 body ที่หยุดส่งกลางทางได้ 408 แล้วถูกปิด
+            let mut stalled = TcpStream::connect(addr).await.unwrap();
+            stalled
+                .write_all(b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nab")
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stalled.read_to_string(&mut response).await.unwrap();
+            assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+            assert!(response.contains("Connection: close\r\n"));
+
+            tokio::task::spawn_blocking(move || shutdown.shutdown())
+                .await
+                .unwrap();
+            assert!(running.await.unwrap().is_ok());
+        });
+    }
+}
@@ -0,0 +1,359 @@
+use crate::http::{Error, Headers, Limits, Result};
+use std::io::{BufRead, Read};
+
+/// 📦 อ่าน request body ตาม `Transfer-Encoding: chunked` หรือ `Content-Length`
+///
+/// # Arguments
+/// * `headers` - headers ของ request ที่ parse แล้ว
+/// * `source` - ข้อมูลต่อจาก header block (อ่านเท่าที่เป็น body เท่านั้น)
+/// * `limits` - body เกิน `max_body_size` ได้ `Error::PayloadTooLarge`
+///   และบรรทัด chunk-size / trailer ยาวไม่เกิน `max_line_length`
+pub fn read_body<R: BufRead>(headers: &Headers, source: R, limits: &Limits) -> Result<Vec<u8>> {
+    if is_chunked(headers)? {
+        return read_chunked(source, limits);
+    }
+
+    match content_length(headers)? {
+        Some(length) => read_sized(source, length, limits.max_body_size),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// body ส่งมาแบบ `Transfer-Encoding: chunked` หรือไม่
+///
+/// # Returns
+/// * `Err(Error::UnsupportedEncoding)` - coding อื่นนอกจาก `chunked` อย่างเดียว (ตอบ 501)
+/// * `Err(Error::InvalidBody)` - มีทั้ง `Transfer-Encoding` และ `Content-Length`
+pub fn is_chunked(headers: &Headers) -> Result<bool> {
+    if !headers.contains("Transfer-Encoding") {
+        return Ok(false);
+    }
+
+    // 🧩 รวมทุกบรรทัด: `chunked` แล้วตามด้วย `Transfer-Encoding: gzip` ต้องไม่ผ่านเป็น chunked
+    let codings: Vec<&str> = headers
+        .get_all("Transfer-Encoding")
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|coding| !coding.is_empty())
+        .collect();
+    // 🗜️ ไม่มีการถอด gzip/deflate ให้ handler จึงรับแค่ `chunked` ตัวเดียว
+    if !matches!(codings[..], [coding] if coding.eq_ignore_ascii_case("chunked")) {
+        return Err(Error::UnsupportedEncoding(codings.join(", ")));
+    }
+    // 🚫 RFC 9112 §6.1: มีทั้งสองแบบคือช่องทาง request smuggling ผ่าน proxy
+    if headers.contains("Content-Length") {
+        return Err(Error::InvalidBody(
+            "both Transfer-Encoding and Content-Length".to_string(),
+        ));
+    }
+    Ok(true)
+}
+
+/// ค่าของ `Content-Length` (ถ้ามีหลายค่าต้องตรงกันทั้งหมด)
+pub fn content_length(headers: &Headers) -> Result<Option<usize>> {
+    let mut length = None;
+
+    for value in headers.get_all("Content-Length") {
+        let parsed: usize = value
+            .trim()
+            .parse()
+            .map_err(|_| Error::InvalidBody(format!("invalid Content-Length `{}`", value)))?;
+
+        if length.is_some_and(|length| length != parsed) {
+            return Err(Error::InvalidBody("conflicting Content-Length".to_string()));
+        }
+        length = Some(parsed);
+    }
+
+    Ok(length)
+}
+
+fn read_sized<R: Read>(source: R, length: usize, max_size: usize) -> Result<Vec<u8>> {
+    if length > max_size {
+        return Err(Error::PayloadTooLarge);
+    }
+
+    let mut body = Vec::with_capacity(length);
+    source.take(length as u64).read_to_end(&mut body)?;
+
+    if body.len() < length {
+        return Err(Error::InvalidBody(
+            "body shorter than Content-Length".to_string(),
+        ));
+    }
+
+    Ok(body)
+}
+
+fn read_chunked<R: BufRead>(mut source: R, limits: &Limits) -> Result<Vec<u8>> {
+    let mut decoder = ChunkedDecoder::new(limits);
+    let mut body = Vec::new();
+
+    while !decoder.is_done() {
+        let input = source.fill_buf()?;
+        if input.is_empty() {
+            decoder.finish()?;
+            break;
+        }
+        let used = decoder.decode(input, &mut body)?;
+        source.consume(used);
+    }
+
+    Ok(body)
+}
+
+/// 🧩 ChunkedDecoder: ถอด `Transfer-Encoding: chunked` จาก bytes ที่ป้อนให้ทีละส่วน
+///
+/// ไม่ผูกกับ I/O: reader ป้อนสิ่งที่อยู่ใน buffer (`fill_buf`) แล้ว `consume`
+/// ตามจำนวนที่ decoder ใช้ไป จึงใช้ได้ทั้ง reader แบบ blocking และแบบ async
+pub(crate) struct ChunkedDecoder {
+    state: ChunkState,
+    line: Vec<u8>,       // 📏 บรรทัดที่ยังรับไม่ครบ (chunk-size, CRLF ท้าย chunk, trailer)
+    trailer_size: usize, // 🧾 trailer นับรวมกับ body limit ด้วย
+    max_size: usize,
+    max_line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChunkState {
+    Size,        // chunk-size [; extension] CRLF
+    Data(usize), // data ของ chunk ที่ยังเหลือ
+    DataEnd,     // CRLF ปิดท้าย data
+    Trailer,     // trailer fields จนถึงบรรทัดว่าง
+    Done,
+}
+
+impl ChunkedDecoder {
+    pub(crate) fn new(limits: &Limits) -> Self {
+        Self {
+            state: ChunkState::Size,
+            line: Vec::new(),
+            trailer_size: 0,
+            max_size: limits.max_body_size,
+            max_line: limits.max_line_length,
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.state == ChunkState::Done
+    }
+
+    /// 📥 ถอด `input` ต่อท้าย `body` คืนจำนวน bytes ที่ใช้ไป
+    /// (หยุดที่จุดจบของ chunked body ที่เหลือเป็นของ request ถัดไป)
+    pub(crate) fn decode(&mut self, input: &[u8], body: &mut Vec<u8>) -> Result<usize> {
+        let mut used = 0;
+
+        while used < input.len() && !self.is_done() {
+            let rest = &input[used..];
+            match self.state {
+                ChunkState::Data(remaining) => {
+                    let take = remaining.min(rest.len());
+                    body.extend_from_slice(&rest[..take]);
+                    used += take;
+                    self.state = match remaining - take {
+                        0 => ChunkState::DataEnd,
+                        remaining => ChunkState::Data(remaining),
+                    };
+                }
+                ChunkState::DataEnd => {
+                    self.line.push(rest[0]);
+                    used += 1;
+                    if self.line.len() == 2 {
+                        if self.line != b"\r\n" {
+                            return Err(Error::InvalidBody(
+                                "chunk not terminated by CRLF".to_string(),
+                            ));
+                        }
+                        self.line.clear();
+                        self.state = ChunkState::Size;
+                    }
+                }
+                ChunkState::Size | ChunkState::Trailer => {
+                    let (part, complete) = match rest.iter().position(|&b| b == b'\n') {
+                        Some(end) => (&rest[..=end], true),
+                        None => (rest, false),
+                    };
+                    used += part.len();
+                    self.push_line(part)?;
+                    if complete {
+                        self.end_line(body.len())?;
+                    }
+                }
+                ChunkState::Done => unreachable!("loop stops once the body is done"),
+            }
+        }
+
+        Ok(used)
+    }
+
+    /// 🔌 stream จบก่อนได้ body ครบ: ยอมเฉพาะตอนรอ trailer
+    /// (client บางตัวไม่ส่งบรรทัดว่างปิดท้าย)
+    pub(crate) fn finish(&self) -> Result<()> {
+        match self.state {
+            ChunkState::Done => Ok(()),
+            ChunkState::Trailer if self.line.is_empty() => Ok(()),
+            _ => Err(Error::InvalidBody("chunked body ended early".to_string())),
+        }
+    }
+
+    fn push_line(&mut self, part: &[u8]) -> Result<()> {
+        if self.line.len() + part.len() > self.max_line {
+            return Err(match self.state {
+                ChunkState::Trailer => Error::HeaderTooLarge,
+                _ => Error::InvalidBody("chunk size line too long".to_string()),
+            });
+        }
+        self.line.extend_from_slice(part);
+        Ok(())
+    }
+
+    fn end_line(&mut self, body_len: usize) -> Result<()> {
+        let line = String::from_utf8_lossy(&self.line);
+        let line = line.trim_end();
+
+        if self.state == ChunkState::Trailer {
+            self.trailer_size += self.line.len();
+            if body_len.saturating_add(self.trailer_size) > self.max_size {
+                return Err(Error::PayloadTooLarge);
+            }
+            if line.is_empty() {
+                self.state = ChunkState::Done;
+            }
+            self.line.clear();
+            return Ok(());
+        }
+
+        let size = line.split(';').next().unwrap_or_default().trim();
+        // 🔢 `from_str_radix` ยอมรับ `+` นำหน้า จึงตรวจว่าเป็น hex ล้วนก่อน
+        let size = Some(size)
+            .filter(|size| !size.is_empty() && size.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|size| usize::from_str_radix(size, 16).ok())
+            .ok_or_else(|| Error::InvalidBody(format!("invalid chunk size `{}`", line)))?;
+
+        self.state = if size == 0 {
+            ChunkState::Trailer
+        } else {
+            // 🧮 checked: chunk-size อย่าง `ffffffffffffffff` ต้องได้ 413 ไม่ใช่ overflow
+            body_len
+                .checked_add(size)
+                .filter(|total| *total <= self.max_size)
+                .ok_or(Error::PayloadTooLarge)?;
+            ChunkState::Data(size)
+        };
+        self.line.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(block: &str) -> Headers {
+        Headers::try_from(block).unwrap()
+    }
+
+    #[test]
+    fn reads_content_length_body() {
+        let body = read_body(
+            &headers("Content-Length: 5\r\n"),
+            &b"hello world"[..],
+            &Limits::default(),
+        );
+
+        assert_eq!(body.unwrap(), b"hello");
+    }
+
+    #[test]
+    fn decodes_chunked_body_with_trailers() {
+        let raw = b"5\r\nhello\r\n6;ext=1\r\n world\r\n0\r\nExpires: never\r\n\r\n";
+        let body = read_body(
+            &headers("Transfer-Encoding: chunked\r\n"),
+            &raw[..],
+            &Limits::default(),
+        );
+
+        assert_eq!(body.unwrap(), b"hello world");
+    }
+
+    fn limits(max_body_size: usize) -> Limits {
+        Limits {
+            max_body_size,
+            max_line_length: 32,
+            ..Limits::default()
+        }
+    }
+
+    #[test]
+    fn enforces_max_body_size() {
+        let chunked = read_body(
+            &headers("Transfer-Encoding: chunked\r\n"),
+            &b"4\r\nabcd\r\n4\r\nefgh\r\n0\r\n\r\n"[..],
+            &limits(6),
+        );
+        let sized = read_body(
+            &headers("Content-Length: 10\r\n"),
+            &b"0123456789"[..],
+            &limits(6),
+        );
+        // 🧮 chunk-size ใหญ่จน `body.len() + size` overflow ต้องได้ 413
+        let overflow = read_body(
+            &headers("Transfer-Encoding: chunked\r\n"),
+            &b"1\r\na\r\nffffffffffffffff\r\n"[..],
+            &limits(6),
+        );
+
+        assert!(matches!(chunked, Err(Error::PayloadTooLarge)));
+        assert!(matches!(sized, Err(Error::PayloadTooLarge)));
+        assert!(matches!(overflow, Err(Error::PayloadTooLarge)));
+    }
+
+    #[test]
+    fn rejects_endless_chunk_lines_and_truncated_bodies() {
+        let chunked = headers("Transfer-Encoding: chunked\r\n");
+        let long_size = format!("{}1\r\na\r\n0\r\n\r\n", "0".repeat(40));
+        let long_trailer = format!("0\r\nX-Trailer: {}\r\n\r\n", "a".repeat(40));
+
+        assert!(matches!(
+            read_body(&chunked, long_size.as_bytes(), &limits(1024)),
+            Err(Error::InvalidBody(_))
+        ));
+        assert!(matches!(
+            read_body(&chunked, long_trailer.as_bytes(), &limits(1024)),
+            Err(Error::HeaderTooLarge)
+        ));
+        assert!(matches!(
+            read_body(&chunked, &b"5\r\nhel"[..], &limits(1024)),
+            Err(Error::InvalidBody(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_smuggling_transfer_encodings() {
+        let read =
+            |block: &str| read_body(&headers(block), &b"1\r\na\r\n0\r\n\r\n"[..], &limits(1024));
+
+        // 🧩 บรรทัดที่สองของ `Transfer-Encoding` ต้องถูกนับด้วย
+        assert!(matches!(
+            read("Transfer-Encoding: chunked\r\nTransfer-Encoding: gzip\r\n"),
+            Err(Error::UnsupportedEncoding(_))
+        ));
+        assert!(matches!(
+            read("Transfer-Encoding: gzip, chunked\r\n"),
+            Err(Error::UnsupportedEncoding(_))
+        ));
+        assert!(matches!(
+            read("Transfer-Encoding: chunked\r\nContent-Length: 5\r\n"),
+            Err(Error::InvalidBody(_))
+        ));
+        assert!(matches!(
+            read_body(
+                &headers("Transfer-Encoding: chunked\r\n"),
+                &b"+1\r\na\r\n0\r\n\r\n"[..],
+                &limits(1024)
+            ),
+            Err(Error::InvalidBody(_))
+        ));
+        assert_eq!(read("Transfer-Encoding: Chunked\r\n").unwrap(), b"a");
+    }
+}
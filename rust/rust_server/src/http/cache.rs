@@ -0,0 +1,242 @@
+use crate::http::{Body, HttpStatus, Method, Request, Response};
+use chrono::{DateTime, Utc};
+use std::fmt::Display;
+use std::time::{Duration, SystemTime};
+
+/// 📋 header ที่ 304 ต้องส่งซ้ำจาก 200 เดิม (RFC 9110 §15.4.5)
+const NOT_MODIFIED_HEADERS: [&str; 7] = [
+    "Cache-Control",
+    "Content-Location",
+    "Date",
+    "ETag",
+    "Expires",
+    "Last-Modified",
+    "Vary",
+];
+
+/// 🗄️ CacheControl: นโยบาย `Cache-Control` ของ response
+///
+/// ```
+/// use rust_server::http::CacheControl;
+/// use std::time::Duration;
+///
+/// let policy = CacheControl::new().public().max_age(Duration::from_secs(3600));
+/// assert_eq!(policy.to_string(), "public, max-age=3600");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    directives: Vec<String>,
+}
+
+impl CacheControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 🚫 ห้าม cache เลย (ข้อมูลส่วนตัว, token ฯลฯ)
+    pub fn no_store(self) -> Self {
+        self.directive("no-store")
+    }
+
+    /// 🔁 cache ได้แต่ต้องถาม server ก่อนใช้ทุกครั้ง (ด้วย `ETag`/`Last-Modified`)
+    pub fn no_cache(self) -> Self {
+        self.directive("no-cache")
+    }
+
+    /// 🌍 proxy และ CDN cache ได้
+    pub fn public(self) -> Self {
+        self.directive("public")
+    }
+
+    /// 👤 cache ได้เฉพาะใน browser ของผู้ใช้
+    pub fn private(self) -> Self {
+        self.directive("private")
+    }
+
+    /// ⏱️ ใช้ของใน cache ได้โดยไม่ต้องถามนานเท่านี้
+    pub fn max_age(self, age: Duration) -> Self {
+        self.directive(format!("max-age={}", age.as_secs()))
+    }
+
+    /// ⏱️ `max-age` สำหรับ shared cache (proxy, CDN)
+    pub fn s_maxage(self, age: Duration) -> Self {
+        self.directive(format!("s-maxage={}", age.as_secs()))
+    }
+
+    /// ⌛ หมดอายุแล้วห้ามใช้ของเก่าโดยไม่ถาม server
+    pub fn must_revalidate(self) -> Self {
+        self.directive("must-revalidate")
+    }
+
+    /// 🧊 เนื้อหาไม่มีวันเปลี่ยน (เช่นไฟล์ที่มี hash ในชื่อ)
+    pub fn immutable(self) -> Self {
+        self.directive("immutable")
+    }
+
+    /// ➕ directive อื่นๆ เช่น `stale-while-revalidate=60`
+    pub fn directive(mut self, directive: impl Into<String>) -> Self {
+        self.directives.push(directive.into());
+        self
+    }
+}
+
+impl Display for CacheControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.directives.join(", "))
+    }
+}
+
+/// 🏷️ strong `ETag` จากเนื้อหา (FNV-1a 64 bit: เร็วและได้ค่าเดิมทุกครั้งที่รัน)
+pub fn etag_for_bytes(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325_u64, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("\"{:016x}-{:x}\"", hash, bytes.len())
+}
+
+/// 🏷️ strong `ETag` ของไฟล์จากเวลาแก้ไขและขนาด (ไม่ต้องอ่านเนื้อไฟล์)
+///
+/// ใช้เวลาละเอียดระดับ nanosecond: ไฟล์ที่ถูกเขียนใหม่ในวินาทีเดียวกันด้วยขนาดเท่าเดิม
+/// ต้องได้ ETag ใหม่ ไม่งั้น `If-Range` จะเอา bytes จากไฟล์คนละเวอร์ชันมาต่อกัน
+pub fn etag_for_file(modified: SystemTime, length: u64) -> String {
+    let modified = modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    format!("\"{:x}-{:x}\"", modified.as_nanos(), length)
+}
+
+/// 🕰️ แปลง HTTP-date (`Sun, 06 Nov 1994 08:49:37 GMT`) เป็น `SystemTime`
+pub fn parse_http_date(value: &str) -> Option<SystemTime> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc).into())
+}
+
+/// ✅ ประเมิน `If-None-Match` / `If-Modified-Since` ของ request กับ response ที่จะส่ง
+///
+/// เติม `ETag` ให้ body แบบ bytes ที่ยังไม่มี แล้วถ้า client มีของล่าสุดอยู่แล้ว
+/// คืน 304 Not Modified (ไม่มี body) แทน ใช้กับ GET/HEAD ที่ตอบ 200 เท่านั้น
+pub fn conditional(request: &Request, mut response: Response) -> Response {
+    if !matches!(request.method(), Method::GET | Method::HEAD)
+        || response.status() != &HttpStatus::Ok
+    {
+        return response;
+    }
+
+    if !response.headers().contains("ETag")
+        && let Body::Bytes(bytes) = response.body()
+    {
+        let etag = etag_for_bytes(bytes);
+        response.headers_mut().insert("ETag", etag);
+    }
+
+    if is_fresh(request, &response) {
+        not_modified(&response)
+    } else {
+        response
+    }
+}
+
+/// client มี representation ล่าสุดอยู่แล้วหรือไม่ (RFC 9110 §13.2.2:
+/// มี `If-None-Match` แล้วไม่ต้องดู `If-Modified-Since`)
+fn is_fresh(request: &Request, response: &Response) -> bool {
+    let headers = request.headers();
+
+    if headers.contains("If-None-Match") {
+        let Some(etag) = response.headers().get("ETag") else {
+            return false;
+        };
+        return headers
+            .get_all("If-None-Match")
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || weak_eq(candidate, etag));
+    }
+
+    let since = headers.get("If-Modified-Since").and_then(parse_http_date);
+    let modified = response
+        .headers()
+        .get("Last-Modified")
+        .and_then(parse_http_date);
+    match (since, modified) {
+        (Some(since), Some(modified)) => modified <= since,
+        _ => false,
+    }
+}
+
+/// 🔎 weak comparison: `W/"abc"` เท่ากับ `"abc"`
+fn weak_eq(a: &str, b: &str) -> bool {
+    let opaque = |tag: &str| tag.strip_prefix("W/").unwrap_or(tag).to_string();
+    opaque(a) == opaque(b)
+}
+
+fn not_modified(response: &Response) -> Response {
+    let mut not_modified = Response::new(HttpStatus::NotModified, None);
+    for name in NOT_MODIFIED_HEADERS {
+        for value in response.headers().get_all(name) {
+            not_modified.headers_mut().append(name, value);
+        }
+    }
+    not_modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &str) -> Request {
+        let raw = format!("GET / HTTP/1.1\r\n{}\r\n", headers);
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    fn home() -> Response {
+        Response::new(HttpStatus::Ok, Some("home".to_string()))
+    }
+
+    #[test]
+    fn matching_etag_returns_304() {
+        let etag = etag_for_bytes(b"home");
+        let fresh = conditional(&request(&format!("If-None-Match: W/{}\r\n", etag)), home());
+        let stale = conditional(&request("If-None-Match: \"other\"\r\n"), home());
+
+        assert_eq!(fresh.status(), &HttpStatus::NotModified);
+        assert_eq!(fresh.headers().get("ETag"), Some(etag.as_str()));
+        assert!(fresh.body().is_empty());
+        assert_eq!(stale.status(), &HttpStatus::Ok);
+        assert_eq!(stale.headers().get("ETag"), Some(etag.as_str()));
+    }
+
+    #[test]
+    fn if_modified_since_compares_last_modified() {
+        let modified = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        let response = || {
+            Response::builder(HttpStatus::Ok)
+                .last_modified(modified)
+                .cache_control(&CacheControl::new().no_cache())
+                .body("home")
+                .build()
+        };
+
+        let fresh = conditional(
+            &request("If-Modified-Since: Sun, 06 Nov 1994 08:49:37 GMT\r\n"),
+            response(),
+        );
+        let stale = conditional(
+            &request("If-Modified-Since: Sat, 05 Nov 1994 08:49:37 GMT\r\n"),
+            response(),
+        );
+
+        assert_eq!(fresh.status(), &HttpStatus::NotModified);
+        assert_eq!(fresh.headers().get("Cache-Control"), Some("no-cache"));
+        assert_eq!(stale.status(), &HttpStatus::Ok);
+    }
+
+    #[test]
+    fn file_etag_changes_within_the_same_second() {
+        let modified = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        let rewritten = modified + Duration::from_millis(250);
+
+        assert_ne!(etag_for_file(modified, 10), etag_for_file(rewritten, 10));
+        assert_eq!(etag_for_file(modified, 10), etag_for_file(modified, 10));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::http::{Body, HttpStatus, Request, Response};
+use std::io::{Cursor, Read};
+
+/// 📏 body ที่เล็กกว่านี้ไม่คุ้มที่จะบีบอัด (header ของ gzip ก็ ~20 bytes แล้ว)
+const DEFAULT_MIN_SIZE: u64 = 1024;
+
+/// 📋 content type ที่บีบอัดได้คุ้ม (รูปภาพ/วิดีโอส่วนใหญ่บีบอัดมาแล้ว)
+const DEFAULT_CONTENT_TYPES: [&str; 6] = [
+    "text/*",
+    "application/json",
+    "application/javascript",
+    "application/xml",
+    "application/wasm",
+    "image/svg+xml",
+];
+
+/// 🗜️ content coding ที่ server รองรับ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// `br` (เปิดด้วย cargo feature `brotli`)
+    #[cfg(feature = "brotli")]
+    Brotli,
+    Gzip,
+    /// `deflate` ใน HTTP คือ zlib format (RFC 9110 §8.4.1.2)
+    Deflate,
+}
+
+impl Encoding {
+    /// ลำดับความชอบเมื่อ client ให้ q เท่ากัน
+    const PREFERRED: &[Encoding] = &[
+        #[cfg(feature = "brotli")]
+        Encoding::Brotli,
+        Encoding::Gzip,
+        Encoding::Deflate,
+    ];
+
+    /// ชื่อใน `Accept-Encoding` / `Content-Encoding`
+    pub fn token(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    /// 🌊 ห่อ reader ให้อ่านออกมาเป็นข้อมูลที่บีบอัดแล้ว
+    pub fn encode(&self, reader: Box<dyn Read + Send>) -> Box<dyn Read + Send> {
+        let level = flate2::Compression::default();
+        match self {
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => Box::new(brotli::CompressorReader::new(reader, 4096, 5, 22)),
+            Encoding::Gzip => Box::new(flate2::read::GzEncoder::new(reader, level)),
+            Encoding::Deflate => Box::new(flate2::read::ZlibEncoder::new(reader, level)),
+        }
+    }
+}
+
+/// 🗜️ Compression: บีบอัด response ตาม `Accept-Encoding` ของ client
+///
+/// * เลือก coding ที่ client ให้ q สูงสุด (`gzip`, `deflate` และ `br` เมื่อเปิด feature `brotli`)
+/// * บีบอัดเฉพาะ content type ใน allowlist และ body ที่ใหญ่กว่า `min_size`
+/// * body แบบ bytes ได้ `Content-Length` ใหม่ตามขนาดที่บีบอัดแล้ว
+///   ส่วนไฟล์/stream บีบอัดระหว่างส่งแบบ chunked
+/// * เติม `Vary: Accept-Encoding` ให้ทุก response ที่บีบอัดได้ (cache จะได้แยกเก็บ)
+///
+/// ```
+/// use rust_server::http::Compression;
+///
+/// let compression = Compression::new()
+///     .with_min_size(256)
+///     .allow_content_type("application/x-ndjson");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Compression {
+    min_size: u64,
+    content_types: Vec<String>,
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self {
+            min_size: DEFAULT_MIN_SIZE,
+            content_types: DEFAULT_CONTENT_TYPES.map(String::from).to_vec(),
+        }
+    }
+}
+
+impl Compression {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 📏 ขนาด body ขั้นต่ำที่จะบีบอัด (ค่าเริ่มต้น: 1024 bytes)
+    pub fn with_min_size(mut self, min_size: u64) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// 📋 แทนที่ allowlist ทั้งหมด (รองรับ wildcard แบบ `text/*`)
+    pub fn with_content_types<I, S>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.content_types = content_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// ➕ เพิ่ม content type เข้า allowlist
+    pub fn allow_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_types.push(content_type.into());
+        self
+    }
+
+    /// 🗜️ บีบอัด `response` ถ้าทั้ง client และ response เข้าเงื่อนไข
+    pub fn apply(&self, request: &Request, mut response: Response) -> Response {
+        if !self.is_compressible(&response) {
+            return response;
+        }
+        if !response.headers().contains_token("Vary", "Accept-Encoding") {
+            response.headers_mut().append("Vary", "Accept-Encoding");
+        }
+
+        // ✂️ Range ต้องนับ byte ของเนื้อหาเดิม จึงไม่บีบอัดเมื่อ client ขอเป็นช่วง
+        if request.headers().contains("Range") {
+            return response;
+        }
+        let accept = request.headers().get_all("Accept-Encoding");
+        let Some(encoding) = negotiate(accept) else {
+            return response;
+        };
+
+        let (status, mut headers, body) = response.into_parts();
+        let body = match body {
+            Body::Bytes(bytes) => {
+                let mut compressed = Vec::new();
+                let read = encoding
+                    .encode(Box::new(Cursor::new(bytes)))
+                    .read_to_end(&mut compressed);
+                if let Err(e) = read {
+                    eprintln!("⚠️ Failed to compress response: {}", e);
+                    return Response::new(HttpStatus::InternalServerError, None);
+                }
+                Body::Bytes(compressed)
+            }
+            Body::File { file, length } => Body::Stream {
+                reader: encoding.encode(Box::new(file.take(length))),
+                length: None,
+            },
+            Body::Stream { reader, .. } => Body::Stream {
+                reader: encoding.encode(reader),
+                length: None,
+            },
+            Body::Empty => Body::Empty,
+        };
+
+        headers.insert("Content-Encoding", encoding.token());
+        headers.remove("Content-Length");
+        // 🏷️ representation ใหม่ต้องมี ETag ต่างจากเดิม (`"abc"` -> `"abc-gzip"`)
+        if let Some(etag) = headers.get("ETag").and_then(|etag| etag.strip_suffix('"')) {
+            let etag = format!("{}-{}\"", etag, encoding.token());
+            headers.insert("ETag", etag);
+        }
+
+        Response::from_parts(status, headers, body)
+    }
+
+    /// response นี้ควรบีบอัดไหม (ยังไม่ดูว่า client รับได้หรือเปล่า)
+    fn is_compressible(&self, response: &Response) -> bool {
+        let status = response.status();
+        if !status.is_success()
+            || status.forbids_body()
+            || status == &HttpStatus::PartialContent
+            || response.headers().contains("Content-Encoding")
+        {
+            return false;
+        }
+
+        // ❔ stream ที่ไม่รู้ความยาวถือว่าใหญ่พอ
+        if response.body().len().is_some_and(|len| len < self.min_size) {
+            return false;
+        }
+
+        let Some(content_type) = response.headers().get("Content-Type") else {
+            return false;
+        };
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        // 📡 SSE ต้องถึง client ทีละ event ทันที บีบอัดแล้วจะไปค้างใน buffer ของ encoder
+        if media_type == "text/event-stream" {
+            return false;
+        }
+        self.content_types
+            .iter()
+            .any(|allowed| match allowed.strip_suffix('*') {
+                Some(prefix) => media_type.starts_with(&prefix.to_ascii_lowercase()),
+                None => allowed.eq_ignore_ascii_case(&media_type),
+            })
+    }
+}
+
+/// 🤝 เลือก coding จาก `Accept-Encoding` เช่น `gzip;q=0.8, br, *;q=0.1`
+/// coding ที่ไม่ได้ระบุได้ q ของ `*` (ถ้ามี) ส่วน q=0 แปลว่าห้ามใช้
+fn negotiate<'a>(accept: impl Iterator<Item = &'a str>) -> Option<Encoding> {
+    let offers: Vec<(String, f32)> = accept
+        .flat_map(|value| value.split(','))
+        .filter_map(|item| {
+            let mut params = item.split(';');
+            let coding = params.next()?.trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!coding.is_empty()).then_some((coding, quality))
+        })
+        .collect();
+
+    let quality_of = |token: &str| {
+        let find = |name: &str| offers.iter().find(|(coding, _)| coding == name);
+        find(token).or_else(|| find("*")).map(|(_, q)| *q)
+    };
+
+    let mut best: Option<(Encoding, f32)> = None;
+    for &encoding in Encoding::PREFERRED {
+        if let Some(quality) = quality_of(encoding.token())
+            && quality > 0.0
+            && best.is_none_or(|(_, best)| quality > best)
+        {
+            best = Some((encoding, quality));
+        }
+    }
+    best.map(|(encoding, _)| encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::{GzDecoder, ZlibDecoder};
+
+    fn request(accept_encoding: &str) -> Request {
+        let raw = format!(
+            "GET / HTTP/1.1\r\nAccept-Encoding: {}\r\n\r\n",
+            accept_encoding
+        );
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    fn page(content_type: &str, size: usize) -> Response {
+        Response::builder(HttpStatus::Ok)
+            .header("Content-Type", content_type)
+            .body("a".repeat(size))
+            .build()
+    }
+
+    fn body_bytes(response: &Response) -> &[u8] {
+        match response.body() {
+            Body::Bytes(bytes) => bytes,
+            _ => panic!("expected bytes body"),
+        }
+    }
+
+    #[test]
+    fn negotiates_by_quality() {
+        let negotiate = |header: &str| negotiate(std::iter::once(header));
+
+        assert_eq!(negotiate("gzip, deflate"), Some(Encoding::Gzip));
+        assert_eq!(negotiate("gzip;q=0.5, deflate"), Some(Encoding::Deflate));
+        assert_eq!(
+            negotiate("*;q=0.3, gzip;q=0, br;q=0"),
+            Some(Encoding::Deflate)
+        );
+        assert_eq!(negotiate("identity"), None);
+        #[cfg(feature = "brotli")]
+        assert_eq!(negotiate("gzip, br"), Some(Encoding::Brotli));
+    }
+
+    #[test]
+    fn compresses_allowed_text_above_threshold() {
+        let compression = Compression::new();
+        let gzip = compression.apply(&request("gzip"), page("text/html; charset=utf-8", 4096));
+        let deflate = compression.apply(&request("deflate"), page("application/json", 4096));
+
+        assert_eq!(gzip.headers().get("Content-Encoding"), Some("gzip"));
+        assert_eq!(gzip.headers().get("Vary"), Some("Accept-Encoding"));
+        let mut html = String::new();
+        GzDecoder::new(body_bytes(&gzip))
+            .read_to_string(&mut html)
+            .unwrap();
+        assert_eq!(html, "a".repeat(4096));
+        assert!(gzip.body().len().unwrap() < 4096);
+
+        let mut json = String::new();
+        ZlibDecoder::new(body_bytes(&deflate))
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json.len(), 4096);
+    }
+
+    #[test]
+    fn skips_small_or_disallowed_bodies() {
+        let compression = Compression::new();
+        let small = compression.apply(&request("gzip"), page("text/plain", 100));
+        let image = compression.apply(&request("gzip"), page("image/png", 4096));
+        let identity = compression.apply(&request("identity"), page("text/plain", 4096));
+
+        assert!(!small.headers().contains("Content-Encoding"));
+        assert!(!image.headers().contains("Content-Encoding"));
+        assert!(!image.headers().contains("Vary"));
+        // 🔁 บีบอัดได้แต่ client ไม่รับ: ยังต้องมี Vary ให้ cache แยกเก็บ
+        assert!(!identity.headers().contains("Content-Encoding"));
+        assert_eq!(identity.headers().get("Vary"), Some("Accept-Encoding"));
+    }
+}
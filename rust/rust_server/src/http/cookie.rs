@@ -0,0 +1,393 @@
+use crate::http::Headers;
+use crate::http::query_string::percent_decode;
+use crate::http::response::format_http_date;
+use std::fmt::{Display, Write as _};
+use std::time::{Duration, SystemTime};
+
+/// 🍪 Cookie: คู่ name/value พร้อม attribute ของ `Set-Cookie`
+///
+/// value ที่มีอักขระนอก cookie-octet (ช่องว่าง, `;`, `,`, `"`, `\`, non-ASCII)
+/// หรือ `%` จะถูก percent-encode ตอนส่ง และ `CookieJar` decode กลับให้ตอนอ่าน
+///
+/// ```
+/// use rust_server::http::{Cookie, SameSite};
+/// use std::time::Duration;
+///
+/// let cookie = Cookie::new("theme", "dark mode")
+///     .with_path("/")
+///     .with_max_age(Duration::from_secs(3600))
+///     .with_http_only(true)
+///     .with_same_site(SameSite::Lax);
+/// assert_eq!(
+///     cookie.to_string(),
+///     "theme=dark%20mode; Path=/; Max-Age=3600; HttpOnly; SameSite=Lax"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    path: Option<String>,
+    domain: Option<String>,
+    max_age: Option<Duration>,
+    expires: Option<SystemTime>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+/// 🧭 `SameSite`: ส่ง cookie ไปกับ request ข้ามเว็บเมื่อไหร่
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict, // 🔒 เฉพาะ request จากเว็บเดียวกัน
+    Lax,    // 🔗 รวม navigation (คลิก link) จากเว็บอื่น
+    None,   // 🌍 ทุก request (browser บังคับให้ต้อง `Secure`)
+}
+
+impl Display for SameSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        })
+    }
+}
+
+impl Cookie {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            ..Self::default()
+        }
+    }
+
+    /// 🗑️ cookie ที่สั่งให้ browser ลบ `name` ทิ้ง (`Path`/`Domain` ต้องตรงกับตอนตั้ง)
+    pub fn removal(name: impl Into<String>) -> Self {
+        Self::new(name, "")
+            .with_max_age(Duration::ZERO)
+            .with_expires(SystemTime::UNIX_EPOCH)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// ⏳ อายุนับจากตอนที่ browser ได้รับ (ถ้ามีทั้งคู่ browser ใช้ตัวนี้ก่อน `Expires`)
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// 📅 วันหมดอายุแบบเวลาจริง (สำหรับ client เก่าที่ไม่รู้จัก `Max-Age`)
+    pub fn with_expires(mut self, expires: SystemTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// 🔐 ส่งกลับมาเฉพาะบน HTTPS
+    pub fn with_secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// 🙈 JavaScript อ่านไม่ได้ (`document.cookie`)
+    pub fn with_http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    pub fn with_same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+}
+
+/// 📤 ค่าของ header `Set-Cookie`
+impl Display for Cookie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", token(&self.name), encode_value(&self.value))?;
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", attribute(path))?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", attribute(domain))?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if let Some(expires) = self.expires {
+            write!(f, "; Expires={}", format_http_date(expires))?;
+        }
+        if self.secure {
+            f.write_str("; Secure")?;
+        }
+        if self.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+        Ok(())
+    }
+}
+
+/// ✂️ ชื่อ cookie ต้องเป็น token (RFC 6265): ตัดอักขระอื่นทิ้ง
+fn token(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(*c))
+        .collect()
+}
+
+/// ✂️ attribute (`Path`, `Domain`) ห้ามมี `;` หรือ control character
+fn attribute(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| *c != ';' && !c.is_control())
+        .collect()
+}
+
+/// 🔡 percent-encode byte ที่ไม่ใช่ cookie-octet (และ `%` เพื่อให้ decode กลับได้ตรง)
+fn encode_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        let octet = matches!(byte, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E);
+        if octet && byte != b'%' {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+/// 🫙 CookieJar: cookie ที่ client ส่งมาใน header `Cookie` (percent-decode แล้ว)
+///
+/// ชื่อเดียวกันมีได้หลายค่า (เช่น cookie ที่ตั้งไว้คนละ `Path`) `get` คืนค่าแรก
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    cookies: Vec<(String, String)>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ค่าแรกของ cookie `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// ทุกค่าของ cookie `name` ตามลำดับที่ได้รับ
+    pub fn get_all<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> {
+        self.cookies
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.cookies
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cookies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cookies.is_empty()
+    }
+}
+
+impl From<&Headers> for CookieJar {
+    /// 📝 `Cookie: a=1; b=2` (รวมหลาย header `Cookie` ถ้า client ส่งแยกมา)
+    fn from(headers: &Headers) -> Self {
+        let cookies = headers
+            .get_all("Cookie")
+            .flat_map(|value| value.split(';'))
+            .filter_map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                let name = name.trim();
+                if name.is_empty() {
+                    return None;
+                }
+                // 🧹 value อาจอยู่ในเครื่องหมายคำพูด (`name="value"`)
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value);
+                let value = String::from_utf8_lossy(&percent_decode(value)).into_owned();
+                Some((name.to_string(), value))
+            })
+            .collect();
+        Self { cookies }
+    }
+}
+
+#[cfg(feature = "signed-cookies")]
+pub use signed::CookieKey;
+
+#[cfg(feature = "signed-cookies")]
+mod signed {
+    use super::{Cookie, CookieJar};
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64;
+    use hmac::{Hmac, KeyInit, Mac};
+    use sha2::Sha256;
+
+    /// 🔏 CookieKey: secret ของ server สำหรับ cookie ที่เซ็นด้วย HMAC-SHA256
+    /// (เปิดด้วย cargo feature `signed-cookies`)
+    ///
+    /// value ที่เซ็นแล้วเป็น `value.signature` client ยังอ่าน value ได้
+    /// แต่แก้ไม่ได้โดยไม่รู้ secret (ลายเซ็นผูกกับชื่อ cookie ด้วย ย้ายไปใช้กับชื่ออื่นไม่ได้)
+    ///
+    /// ```
+    /// use rust_server::http::{Cookie, CookieJar, CookieKey, Headers};
+    ///
+    /// let key = CookieKey::new(b"a long random server secret, 32+ bytes");
+    /// let cookie = key.sign(Cookie::new("user", "42"));
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.append("Cookie", format!("user={}", cookie.value()));
+    /// let jar = CookieJar::from(&headers);
+    /// assert_eq!(key.verify(&jar, "user").as_deref(), Some("42"));
+    /// ```
+    #[derive(Clone)]
+    pub struct CookieKey {
+        secret: Vec<u8>,
+    }
+
+    impl std::fmt::Debug for CookieKey {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("CookieKey(..)") // 🙈 ไม่พิมพ์ secret ลง log
+        }
+    }
+
+    impl CookieKey {
+        /// 🔑 secret ควรสุ่มและยาวอย่างน้อย 32 bytes
+        pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+            Self {
+                secret: secret.into(),
+            }
+        }
+
+        /// ✍️ ต่อท้าย value ด้วยลายเซ็น (attribute อื่นคงเดิม)
+        pub fn sign(&self, mut cookie: Cookie) -> Cookie {
+            let signature = BASE64.encode(
+                self.mac(&cookie.name, &cookie.value)
+                    .finalize()
+                    .into_bytes(),
+            );
+            cookie.value = format!("{}.{}", cookie.value, signature);
+            cookie
+        }
+
+        /// ✅ value ของ cookie `name` ที่ลายเซ็นถูกต้อง (`None` ถ้าไม่มีหรือถูกแก้ไข)
+        pub fn verify(&self, jar: &CookieJar, name: &str) -> Option<String> {
+            jar.get_all(name).find_map(|signed| {
+                let (value, signature) = signed.rsplit_once('.')?;
+                let signature = BASE64.decode(signature).ok()?;
+                // ⏱️ เทียบแบบ constant-time กัน timing attack
+                self.mac(name, value).verify_slice(&signature).ok()?;
+                Some(value.to_string())
+            })
+        }
+
+        fn mac(&self, name: &str, value: &str) -> Hmac<Sha256> {
+            let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+                .expect("HMAC accepts keys of any length");
+            mac.update(name.as_bytes());
+            mac.update(b"=");
+            mac.update(value.as_bytes());
+            mac
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jar(cookie_headers: &[&str]) -> CookieJar {
+        let mut headers = Headers::new();
+        for value in cookie_headers {
+            headers.append("Cookie", *value);
+        }
+        CookieJar::from(&headers)
+    }
+
+    #[test]
+    fn formats_set_cookie_attributes() {
+        let cookie = Cookie::new("session", "a;b\r\n")
+            .with_path("/app")
+            .with_domain("example.com")
+            .with_expires(SystemTime::UNIX_EPOCH + Duration::from_secs(784111777))
+            .with_secure(true)
+            .with_same_site(SameSite::Strict);
+        assert_eq!(
+            cookie.to_string(),
+            "session=a%3Bb%0D%0A; Path=/app; Domain=example.com; \
+             Expires=Sun, 06 Nov 1994 08:49:37 GMT; Secure; SameSite=Strict"
+        );
+        assert_eq!(
+            Cookie::removal("session").with_path("/").to_string(),
+            "session=; Path=/; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn parses_cookie_headers_into_jar() {
+        let jar = jar(&["a=1; b=\"two\";c=x%20y", "a=3; broken; =empty"]);
+
+        assert_eq!(jar.get("a"), Some("1"));
+        assert_eq!(jar.get_all("a").collect::<Vec<_>>(), ["1", "3"]);
+        assert_eq!(jar.get("b"), Some("two"));
+        assert_eq!(jar.get("c"), Some("x y"));
+        assert_eq!(jar.len(), 4);
+        assert!(!jar.contains("broken"));
+    }
+
+    #[cfg(feature = "signed-cookies")]
+    #[test]
+    fn rejects_tampered_and_renamed_signed_cookies() {
+        let key = CookieKey::new(b"secret".to_vec());
+        let signed = key.sign(Cookie::new("user", "42")).value().to_string();
+        let (_, signature) = signed.rsplit_once('.').unwrap();
+
+        let tampered = jar(&[&format!("user=43.{}; other={}", signature, signed)]);
+        assert_eq!(key.verify(&tampered, "user"), None);
+        assert_eq!(key.verify(&tampered, "other"), None);
+        assert_eq!(
+            key.verify(&jar(&[&format!("user={}", signed)]), "user")
+                .as_deref(),
+            Some("42")
+        );
+        assert_eq!(
+            CookieKey::new(b"other".to_vec()).verify(&jar(&[&format!("user={}", signed)]), "user"),
+            None
+        );
+    }
+}
@@ -1,16 +1,38 @@
 use std::convert::From;
 use std::fmt::Display;
+use std::io::ErrorKind;
+
+#[derive(Debug)]
 pub enum Error {
     InvalidRequest,
     InvalidProtocol,
     InvalidMethod,
+    InvalidHeader(String),
+    InvalidBody(String),
+    UnsupportedEncoding(String), // 🧩 `Transfer-Encoding` ที่ไม่ใช่ `chunked` (ตอบ 501)
+    PayloadTooLarge,
+    HeaderTooLarge,
+    Timeout,
     IO(String),
     Utf8(String),
+    Tls(String),
+    WebSocket(String),
 }
 
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
-        Self::IO(error.to_string())
+        match error.kind() {
+            // ⏱️ read timeout ของ socket (ขึ้นกับ OS ว่าจะได้ kind ไหน)
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => Self::Timeout,
+            _ => Self::IO(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+impl From<rustls::Error> for Error {
+    fn from(error: rustls::Error) -> Self {
+        Self::Tls(error.to_string())
     }
 }
 
@@ -22,14 +44,22 @@ impl From<std::str::Utf8Error> for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let message = match self {
-            Error::InvalidRequest => "Invalid Request",
-            Error::InvalidProtocol => "Invalid Protocol",
-            Error::InvalidMethod => "Invalid Method",
-            Error::IO(msg) => msg,
-            Error::Utf8(msg) => msg,
-        };
-
-        write!(f, "Error: {}", message)
+        match self {
+            Error::InvalidRequest => write!(f, "Error: Invalid Request"),
+            Error::InvalidProtocol => write!(f, "Error: Invalid Protocol"),
+            Error::InvalidMethod => write!(f, "Error: Invalid Method"),
+            Error::InvalidHeader(line) => write!(f, "Error: Invalid Header `{}`", line),
+            Error::InvalidBody(reason) => write!(f, "Error: Invalid Body ({})", reason),
+            Error::UnsupportedEncoding(coding) => {
+                write!(f, "Error: Unsupported Transfer-Encoding `{}`", coding)
+            }
+            Error::PayloadTooLarge => write!(f, "Error: Payload Too Large"),
+            Error::HeaderTooLarge => write!(f, "Error: Request Header Fields Too Large"),
+            Error::Timeout => write!(f, "Error: Timed Out"),
+            Error::IO(msg) => write!(f, "Error: {}", msg),
+            Error::Utf8(msg) => write!(f, "Error: {}", msg),
+            Error::Tls(msg) => write!(f, "Error: TLS {}", msg),
+            Error::WebSocket(msg) => write!(f, "Error: WebSocket {}", msg),
+        }
     }
 }
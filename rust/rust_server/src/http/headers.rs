@@ -0,0 +1,137 @@
+use crate::http::{Error, Result};
+
+/// 📋 HTTP headers: เก็บ header ตามลำดับที่ได้รับ และค้นหาแบบ case-insensitive
+/// header ชื่อเดียวกันสามารถมีได้หลายค่า (เช่น `Accept`, `Set-Cookie`)
+#[derive(Debug, Clone, Default)]
+pub struct Headers {
+    entries: Vec<(String, String)>,
+}
+
+impl Headers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ค่าแรกของ header `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// ทุกค่าของ header `name` ตามลำดับที่ได้รับ
+    pub fn get_all<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// 🔎 header แบบ list คั่นด้วย comma มี `token` อยู่หรือไม่ (เช่น `Connection: keep-alive, Upgrade`)
+    pub fn contains_token(&self, name: &str, token: &str) -> bool {
+        self.get_all(name)
+            .flat_map(|value| value.split(','))
+            .any(|item| item.trim().eq_ignore_ascii_case(token))
+    }
+
+    /// เพิ่มค่าใหม่โดยไม่ลบค่าเดิมของ header ชื่อเดียวกัน
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((name.into(), value.into()));
+    }
+
+    /// แทนที่ทุกค่าของ header `name` ด้วยค่าเดียว
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        self.remove(&name);
+        self.entries.push((name, value.into()));
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.entries
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl TryFrom<&str> for Headers {
+    type Error = Error;
+
+    /// 📝 Parse header block (ไม่รวม request line) จนถึงบรรทัดว่าง
+    fn try_from(block: &str) -> Result<Self> {
+        let mut headers = Headers::new();
+
+        for line in block.split("\r\n") {
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = parse_line(line)?;
+            headers.append(name, value);
+        }
+
+        Ok(headers)
+    }
+}
+
+/// 📝 แยก header 1 บรรทัดเป็น `(name, value)` โดยยืมจาก `line` (ไม่ allocate)
+pub(crate) fn parse_line(line: &str) -> Result<(&str, &str)> {
+    let invalid = || Error::InvalidHeader(line.to_string());
+
+    let (name, value) = line.split_once(':').ok_or_else(invalid)?;
+
+    // RFC 9112: ห้ามมี whitespace ระหว่างชื่อ header กับ ':' และชื่อต้องเป็น token
+    if name.is_empty() || !name.bytes().all(is_token) {
+        return Err(invalid());
+    }
+
+    Ok((name, value.trim_matches([' ', '\t'])))
+}
+
+/// 🔤 tchar ตาม RFC 9110 (ใช้ได้ทั้งชื่อ header และชื่อ method)
+pub(crate) fn is_token(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookups_are_case_insensitive_and_multi_value() {
+        let headers =
+            Headers::try_from("Host: localhost\r\nAccept: text/html\r\naccept: */*\r\n\r\n")
+                .unwrap();
+
+        assert_eq!(headers.get("host"), Some("localhost"));
+        assert_eq!(headers.get("ACCEPT"), Some("text/html"));
+        assert_eq!(
+            headers.get_all("Accept").collect::<Vec<_>>(),
+            ["text/html", "*/*"]
+        );
+        assert!(!headers.contains("Content-Length"));
+    }
+
+    #[test]
+    fn malformed_line_is_reported() {
+        let result = Headers::try_from("Host: localhost\r\nBad Header: x\r\n");
+
+        match result {
+            Err(Error::InvalidHeader(line)) => assert_eq!(line, "Bad Header: x"),
+            _ => panic!("expected InvalidHeader"),
+        }
+    }
+}
@@ -1,24 +1,58 @@
+use crate::http::headers::is_token;
 use crate::http::{Error, Result};
+use std::fmt::Display;
 use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Method {
     GET,
     POST,
     PUT,
     DELETE,
+    HEAD,
+    OPTIONS,
+    PATCH,
+    TRACE,
+    CONNECT,
+    /// 🧩 extension method ที่ไม่อยู่ในรายการข้างบน (เช่น WebDAV `PROPFIND`)
+    Extension(String),
 }
 
 impl FromStr for Method {
     type Err = Error;
 
+    /// method เป็น case-sensitive (RFC 9110) และต้องเป็น token ที่ถูกต้อง
     fn from_str(s: &str) -> Result<Self> {
         match s {
             "GET" => Ok(Method::GET),
             "POST" => Ok(Method::POST),
             "PUT" => Ok(Method::PUT),
             "DELETE" => Ok(Method::DELETE),
+            "HEAD" => Ok(Method::HEAD),
+            "OPTIONS" => Ok(Method::OPTIONS),
+            "PATCH" => Ok(Method::PATCH),
+            "TRACE" => Ok(Method::TRACE),
+            "CONNECT" => Ok(Method::CONNECT),
+            _ if !s.is_empty() && s.bytes().all(is_token) => Ok(Method::Extension(s.to_string())),
             _ => Err(Error::InvalidMethod),
         }
     }
 }
+
+impl Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let method = match self {
+            Method::GET => "GET",
+            Method::POST => "POST",
+            Method::PUT => "PUT",
+            Method::DELETE => "DELETE",
+            Method::HEAD => "HEAD",
+            Method::OPTIONS => "OPTIONS",
+            Method::PATCH => "PATCH",
+            Method::TRACE => "TRACE",
+            Method::CONNECT => "CONNECT",
+            Method::Extension(method) => method,
+        };
+        write!(f, "{}", method)
+    }
+}
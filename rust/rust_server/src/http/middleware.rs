@@ -0,0 +1,229 @@
+use crate::http::{Handler, HttpStatus, Request, Response};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Instant, SystemTime};
+
+/// 🧅 Middleware: โค้ดที่ครอบรอบ `Handler` ทุก request
+///
+/// เรียก `next.run(request)` เพื่อส่งต่อให้ middleware ถัดไป (และ handler ในที่สุด)
+/// หรือคืน response เองเลยเพื่อตัดจบ (short-circuit) ก่อนถึง handler
+/// แก้ request ได้ก่อนส่งต่อ และแก้ response ได้หลัง `next.run` คืนค่า
+///
+/// closure `Fn(&mut Request, Next) -> Response` ใช้เป็น middleware ได้เลย:
+///
+/// ```
+/// use rust_server::http::{HttpStatus, Next, Request, Response, Router, Server};
+///
+/// let server = Server::new("127.0.0.1:8000".to_string(), Router::new())
+///     // 🔐 ไม่มี token ตอบ 401 ทันที ไม่ถึง handler
+///     .with_middleware(|request: &mut Request, next: Next| {
+///         if !request.headers().contains("Authorization") {
+///             return Response::new(HttpStatus::Unauthorized, None);
+///         }
+///         next.run(request).with_header("X-Authenticated", "yes")
+///     });
+/// ```
+pub trait Middleware: Send + Sync {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response;
+}
+
+impl<F> Middleware for F
+where
+    F: Fn(&mut Request, Next<'_>) -> Response + Send + Sync,
+{
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        self(request, next)
+    }
+}
+
+/// ⏭️ ส่วนที่เหลือของ chain: middleware ที่ยังไม่ได้เรียก ตามด้วย handler
+pub struct Next<'a> {
+    chain: &'a [Arc<dyn Middleware>],
+    handler: &'a mut dyn Handler,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(chain: &'a [Arc<dyn Middleware>], handler: &'a mut dyn Handler) -> Self {
+        Self { chain, handler }
+    }
+
+    /// ▶️ เรียก middleware ถัดไป (หรือ handler ถ้าครบ chain แล้ว)
+    pub fn run(self, request: &mut Request) -> Response {
+        match self.chain.split_first() {
+            Some((middleware, rest)) => middleware.handle(request, Next::new(rest, self.handler)),
+            None => {
+                self.handler.prepare_request(request);
+                self.handler.handle_request(request)
+            }
+        }
+    }
+}
+
+/// 🆔 RequestId: ให้ทุก request มี id ไว้ตามรอยใน log
+///
+/// ใช้ id ที่ client (หรือ proxy) ส่งมาถ้าดูปลอดภัย ไม่งั้นสร้างใหม่
+/// แล้วใส่ไว้ทั้งใน request (ให้ handler อ่านได้) และใน response
+#[derive(Debug, Clone)]
+pub struct RequestId {
+    header: String,
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new("X-Request-Id")
+    }
+}
+
+impl RequestId {
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+        }
+    }
+
+    fn generate() -> String {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        format!("{:016x}-{:06x}", nanos, count & 0xff_ffff)
+    }
+}
+
+impl Middleware for RequestId {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        // 🛡️ id จากภายนอกต้องสั้นและเป็นตัวอักษรธรรมดา (กัน log injection)
+        let id = request
+            .headers()
+            .get(&self.header)
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= 128
+                    && id
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b"-_.:".contains(&b))
+            })
+            .map_or_else(Self::generate, str::to_string);
+
+        request
+            .headers_mut()
+            .insert(self.header.as_str(), id.as_str());
+        next.run(request).with_header(self.header.as_str(), id)
+    }
+}
+
+/// ⏱️ Timing: เวลาที่ใช้สร้าง response ใน `Server-Timing` และ `X-Response-Time`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timing;
+
+impl Middleware for Timing {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let started = Instant::now();
+        let response = next.run(request);
+        let millis = started.elapsed().as_secs_f64() * 1000.0;
+
+        response
+            .with_header("Server-Timing", format!("app;dur={:.3}", millis))
+            .with_header("X-Response-Time", format!("{:.3}ms", millis))
+    }
+}
+
+/// 🛟 CatchPanic: handler ที่ panic ได้ 500 แทนที่ connection จะหลุดไปเฉยๆ
+///
+/// ควรใส่เป็น middleware ตัวแรก (นอกสุด) เพื่อให้ครอบ middleware ตัวอื่นด้วย
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CatchPanic;
+
+impl Middleware for CatchPanic {
+    fn handle(&self, request: &mut Request, next: Next<'_>) -> Response {
+        let path = request.path().clone();
+        // handler ที่ panic กลางทางอาจทิ้ง state ไว้ครึ่งๆ กลางๆ แต่ดีกว่าปล่อย client ค้าง
+        match panic::catch_unwind(AssertUnwindSafe(|| next.run(request))) {
+            Ok(response) => response,
+            Err(_) => {
+                eprintln!("⚠️ Handler panicked while serving {}", path);
+                Response::new(HttpStatus::InternalServerError, None)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::Router;
+
+    fn request(raw: &str) -> Request {
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .get("/", |req| {
+                let user = req.headers().get("X-User").unwrap_or("nobody");
+                Response::new(HttpStatus::Ok, Some(user.to_string()))
+            })
+            .get("/boom", |_| panic!("boom"))
+    }
+
+    fn run(chain: Vec<Arc<dyn Middleware>>, raw: &str) -> Response {
+        let mut router = router();
+        Next::new(&chain, &mut router).run(&mut request(raw))
+    }
+
+    #[test]
+    fn runs_in_order_and_can_modify_request_and_response() {
+        let chain: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(|request: &mut Request, next: Next| {
+                request.headers_mut().insert("X-User", "petch");
+                next.run(request).with_header("X-Order", "outer")
+            }),
+            Arc::new(|request: &mut Request, next: Next| {
+                next.run(request).with_header("X-Order", "inner")
+            }),
+        ];
+        let response = run(chain, "GET / HTTP/1.1\r\n\r\n");
+
+        // 🧅 ตัวนอกสุดแก้ response ทีหลังสุด
+        assert_eq!(response.headers().get("X-Order"), Some("outer"));
+        assert_eq!(response.body().len(), Some("petch".len() as u64));
+    }
+
+    #[test]
+    fn short_circuits_before_handler() {
+        let chain: Vec<Arc<dyn Middleware>> = vec![Arc::new(|_: &mut Request, _: Next| {
+            Response::new(HttpStatus::Unauthorized, None)
+        })];
+
+        let response = run(chain, "GET /boom HTTP/1.1\r\n\r\n");
+        assert_eq!(response.status(), &HttpStatus::Unauthorized);
+    }
+
+    #[test]
+    fn built_ins_add_ids_timing_and_recover_from_panics() {
+        let chain: Vec<Arc<dyn Middleware>> = vec![
+            Arc::new(CatchPanic),
+            Arc::new(RequestId::default()),
+            Arc::new(Timing),
+        ];
+
+        let reused = run(
+            chain.clone(),
+            "GET / HTTP/1.1\r\nX-Request-Id: abc-123\r\n\r\n",
+        );
+        assert_eq!(reused.headers().get("X-Request-Id"), Some("abc-123"));
+        assert!(reused.headers().contains("Server-Timing"));
+
+        let generated = run(
+            chain.clone(),
+            "GET / HTTP/1.1\r\nX-Request-Id: bad\"id\r\n\r\n",
+        );
+        assert_ne!(generated.headers().get("X-Request-Id"), Some("bad\"id"));
+
+        let panicked = run(chain, "GET /boom HTTP/1.1\r\n\r\n");
+        assert_eq!(panicked.status(), &HttpStatus::InternalServerError);
+    }
+}
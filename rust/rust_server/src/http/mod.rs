@@ -1,17 +1,59 @@
+pub mod access_log;
+#[cfg(feature = "tokio")]
+pub mod async_reader;
+#[cfg(feature = "tokio")]
+pub mod async_server;
+pub mod body;
+pub mod cache;
+pub mod compression;
+pub mod cookie;
 pub mod error;
+pub mod headers;
 pub mod method;
+pub mod middleware;
 pub mod query_string;
+pub mod range;
+pub mod reader;
 pub mod request;
 pub mod response;
+pub mod router;
 pub mod server;
+pub mod shutdown;
+pub mod sse;
+pub mod static_files;
 pub mod status;
+pub mod thread_pool;
+#[cfg(feature = "tls")]
+pub mod tls;
+pub mod websocket;
 
+pub use access_log::{AccessEntry, AccessLog, LogFormat, LogSink};
+#[cfg(feature = "tokio")]
+pub use async_reader::AsyncRequestReader;
+#[cfg(feature = "tokio")]
+pub use async_server::{AsyncHandler, AsyncServer};
+pub use cache::CacheControl;
+pub use compression::Compression;
+#[cfg(feature = "signed-cookies")]
+pub use cookie::CookieKey;
+pub use cookie::{Cookie, CookieJar, SameSite};
 pub use error::Error;
+pub use headers::Headers;
 pub use method::Method;
+pub use middleware::{CatchPanic, Middleware, Next, RequestId, Timing};
 pub use query_string::QueryString;
-pub use request::Request;
-pub use response::Response;
-pub use server::Server;
+pub use reader::{Limits, RequestReader};
+pub use request::{Request, RequestRef};
+pub use response::{Body, Response, ResponseBuilder};
+pub use router::Router;
+pub use server::{Handler, Server};
+pub use shutdown::ShutdownHandle;
+pub use sse::{Event, EventSender, EventStream};
+pub use static_files::StaticFiles;
 pub use status::HttpStatus;
+pub use thread_pool::ThreadPool;
+#[cfg(feature = "tls")]
+pub use tls::TlsConfig;
+pub use websocket::WebSocket;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,28 +1,168 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::str::FromStr;
 
-#[derive(Debug)]
+/// 🔍 QueryString: key/value จาก `?a=1&b=2` (หรือ form body) ที่ percent-decode แล้ว
+///
+/// เก็บทุกคู่ตามลำดับที่มา: key ซ้ำได้ (`?tag=a&tag=b`) และ key ที่ไม่มี `=`
+/// (flag เช่น `?debug`) ถูกเก็บไว้พร้อมค่าว่าง
+///
+/// ```
+/// use rust_server::http::QueryString;
+///
+/// let qs = QueryString::from("tag=a&tag=b&q=hello+world&page=2&debug");
+/// assert_eq!(qs.get("q"), Some("hello world"));
+/// assert_eq!(qs.get_all("tag").collect::<Vec<_>>(), ["a", "b"]);
+/// assert_eq!(qs.parse::<u32>("page"), Some(Ok(2)));
+/// assert!(qs.contains("debug"));
+/// ```
+#[derive(Debug, Clone, Default)]
 pub struct QueryString {
-    data: HashMap<String, String>,
+    entries: Vec<(String, String)>,
 }
 
 impl QueryString {
-    pub fn get(&self, key: &String) -> Option<&String> {
-        self.data.get(key)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 🔍 ค่าแรกของ `key` (flag ที่ไม่มี `=` ได้ `Some("")`)
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.get_all(key).next()
+    }
+
+    /// 📚 ทุกค่าของ `key` ตามลำดับที่มา
+    pub fn get_all<'a>(&'a self, key: &str) -> impl Iterator<Item = &'a str> {
+        self.entries
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 🔢 แปลงค่าแรกของ `key` เป็น type ใดก็ได้ที่ implement `FromStr`
+    ///
+    /// # Returns
+    /// * `None` - ไม่มี `key`
+    /// * `Some(Err(..))` - มี `key` แต่แปลงค่าไม่ได้
+    pub fn parse<T: FromStr>(&self, key: &str) -> Option<Result<T, T::Err>> {
+        self.get(key).map(str::parse)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.iter().any(|(k, _)| k == key)
+    }
+
+    /// ➕ เพิ่มคู่ key/value (key ซ้ำได้)
+    pub fn append(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((key.into(), value.into()));
+    }
+
+    /// 🔁 วนทุกคู่ `(key, value)` ตามลำดับที่มา
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
     }
 }
 
 impl From<&str> for QueryString {
     fn from(value: &str) -> Self {
-        let mut data = HashMap::new();
-
-        for c in value.split('&') {
-            if let Some(i) = c.find('=') {
-                let key = &c[..i];
-                let val = &c[i + 1..];
-                data.insert(key.to_string(), val.to_string());
-            }
+        let entries = parse_pairs(value)
+            .map(|(key, val)| (key.into_owned(), val.into_owned()))
+            .collect();
+
+        QueryString { entries }
+    }
+}
+
+/// 🧩 แยก `a=1&b=2` เป็นคู่ key/value ที่ decode แล้ว
+/// คืน `Cow::Borrowed` จาก `raw` ตรงๆ เมื่อไม่มีอะไรต้อง decode (ไม่ allocate)
+pub(crate) fn parse_pairs(raw: &str) -> impl Iterator<Item = (Cow<'_, str>, Cow<'_, str>)> {
+    raw.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, val)) => (decode_component(key), decode_component(val)),
+            None => (decode_component(pair), Cow::Borrowed("")), // 🚩 flag เช่น `?debug`
+        })
+}
+
+/// 🔡 decode key/value ของ query string: `+` เป็นช่องว่าง แล้ว percent-decode
+fn decode_component(component: &str) -> Cow<'_, str> {
+    if !component.contains(['+', '%']) {
+        return Cow::Borrowed(component);
+    }
+    let bytes = percent_decode(&component.replace('+', " "));
+    Cow::Owned(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 🔡 percent-decode (`%20` -> ` `) เป็น raw bytes
+/// `%` ที่ไม่ตามด้วย hex 2 ตัวถูกเก็บไว้ตามเดิม
+pub(crate) fn percent_decode(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        // 🔎 ตรวจ hex ทีละตัวเอง: `from_str_radix` ยอมรับ `+` นำหน้า (`%+1` จะกลายเป็น 0x01)
+        if bytes[i] == b'%'
+            && let Some(&[high, low]) = bytes.get(i + 1..i + 3)
+            && high.is_ascii_hexdigit()
+            && low.is_ascii_hexdigit()
+        {
+            decoded.push(hex_value(high) << 4 | hex_value(low));
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
         }
+    }
+
+    decoded
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_and_keeps_repeated_and_flag_keys() {
+        let qs = QueryString::from("name=J%C3%BCrgen+M&tag=a&tag=b&debug&a%26b=c%3Dd");
+
+        assert_eq!(qs.get("name"), Some("Jürgen M"));
+        assert_eq!(qs.get_all("tag").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(qs.get("debug"), Some(""));
+        assert_eq!(qs.get("a&b"), Some("c=d"));
+        assert_eq!(qs.get("missing"), None);
+        assert_eq!(qs.len(), 5);
+    }
+
+    #[test]
+    fn parses_typed_values() {
+        let qs = QueryString::from("page=3&size=big");
+
+        assert_eq!(qs.parse::<usize>("page"), Some(Ok(3)));
+        assert!(matches!(qs.parse::<usize>("size"), Some(Err(_))));
+        assert_eq!(qs.parse::<usize>("missing"), None);
+    }
 
-        QueryString { data }
+    #[test]
+    fn keeps_malformed_percent_sequences() {
+        assert_eq!(percent_decode("100%"), b"100%");
+        assert_eq!(percent_decode("%zz%41"), b"%zzA");
+        assert_eq!(percent_decode("%+1"), b"%+1");
+        assert_eq!(percent_decode("%-1%e9"), b"%-1\xe9");
     }
 }
@@ -0,0 +1,331 @@
+use crate::http::cache::parse_http_date;
+use crate::http::{Body, Headers, HttpStatus, Method, Request, Response};
+use std::collections::VecDeque;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// 🚧 จำนวน range สูงสุดต่อ request (กัน client ขอ range ย่อยๆ เป็นพันชิ้น)
+const MAX_RANGES: usize = 16;
+
+/// ✂️ ตอบ `Range: bytes=...` ของ GET ด้วย 206 Partial Content
+///
+/// * range เดียว: body เฉพาะช่วงนั้นพร้อม `Content-Range`
+/// * หลาย range: `multipart/byteranges`
+/// * ไม่มี range ไหนอยู่ในไฟล์เลย: 416 พร้อม `Content-Range: bytes */{length}`
+///
+/// ใช้ได้กับ body แบบ bytes และไฟล์ที่ตอบ 200 (stream อื่น seek ไม่ได้จึงส่งเต็มเหมือนเดิม)
+/// และเติม `Accept-Ranges: bytes` ให้ response ที่รองรับ
+pub fn apply(request: &Request, mut response: Response) -> Response {
+    let rangeable = matches!(response.body(), Body::Bytes(_) | Body::File { .. });
+    if response.status() != &HttpStatus::Ok || !rangeable {
+        return response;
+    }
+    if !response.headers().contains("Accept-Ranges") {
+        response.headers_mut().insert("Accept-Ranges", "bytes");
+    }
+
+    let Some(range) = request.headers().get("Range") else {
+        return response;
+    };
+    if request.method() != &Method::GET || !if_range_matches(request, &response) {
+        return response;
+    }
+    let length = response.body().len().unwrap_or_default();
+    // 🙈 Range ที่ parse ไม่ได้ หรือหน่วยไม่ใช่ bytes ให้ทำเหมือนไม่มี (RFC 9110 §14.2)
+    let Some(ranges) = parse_ranges(range, length) else {
+        return response;
+    };
+
+    if ranges.is_empty() {
+        return Response::new(HttpStatus::RangeNotSatisfiable, None)
+            .with_header("Content-Range", format!("bytes */{}", length));
+    }
+
+    let (_, mut headers, body) = response.into_parts();
+    let body = match body {
+        Body::File { file, .. } => partial(file, &mut headers, &ranges, length),
+        Body::Bytes(bytes) => partial(Cursor::new(bytes), &mut headers, &ranges, length),
+        _ => unreachable!("checked by `rangeable`"),
+    };
+    match body {
+        Ok(body) => Response::from_parts(HttpStatus::PartialContent, headers, body),
+        Err(e) => {
+            eprintln!("⚠️ Failed to seek for range request: {}", e);
+            Response::new(HttpStatus::InternalServerError, None)
+        }
+    }
+}
+
+/// `If-Range` (ถ้ามี) ต้องตรงกับ `ETag` แบบ strong หรือ `Last-Modified` ทุกประการ
+/// ไม่ตรงแปลว่าไฟล์เปลี่ยนไปแล้ว ต้องส่งทั้งไฟล์ใหม่
+fn if_range_matches(request: &Request, response: &Response) -> bool {
+    let Some(condition) = request.headers().get("If-Range") else {
+        return true;
+    };
+
+    if condition.starts_with('"') || condition.starts_with("W/") {
+        return response.headers().get("ETag") == Some(condition) && !condition.starts_with("W/");
+    }
+    let modified = response
+        .headers()
+        .get("Last-Modified")
+        .and_then(parse_http_date);
+    modified.is_some() && modified == parse_http_date(condition)
+}
+
+/// 📐 แปลง `bytes=0-99,200-,-50` เป็นช่วงที่อยู่ในไฟล์ยาว `length` bytes
+///
+/// # Returns
+/// * `None` - header ไม่ถูกต้อง หรือไม่ใช่หน่วย `bytes` (ให้ส่งทั้งไฟล์)
+/// * `Some(vec![])` - ถูกต้องแต่ไม่มีช่วงไหนอยู่ในไฟล์เลย (416)
+fn parse_ranges(header: &str, length: u64) -> Option<Vec<RangeInclusive<u64>>> {
+    let (unit, specs) = header.split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+
+    let mut ranges = Vec::new();
+    for spec in specs
+        .split(',')
+        .map(str::trim)
+        .filter(|spec| !spec.is_empty())
+    {
+        let (first, last) = spec.split_once('-')?;
+        let range = match (first.trim(), last.trim()) {
+            // `-500`: 500 bytes สุดท้าย
+            ("", suffix) => {
+                let suffix: u64 = suffix.parse().ok()?;
+                (suffix > 0 && length > 0).then(|| length.saturating_sub(suffix)..=length - 1)
+            }
+            // `500-` หรือ `500-999`
+            (first, last) => {
+                let first: u64 = first.parse().ok()?;
+                let last = match last {
+                    "" => u64::MAX,
+                    last => last.parse().ok()?,
+                };
+                if last < first {
+                    return None;
+                }
+                (first < length).then(|| first..=last.min(length - 1))
+            }
+        };
+        ranges.extend(range);
+    }
+
+    if ranges.len() > MAX_RANGES {
+        return None;
+    }
+    Some(ranges)
+}
+
+/// 🧩 สร้าง body ของ 206 จาก source ที่ seek ได้ และตั้ง header ที่เกี่ยวข้อง
+fn partial<R>(
+    mut source: R,
+    headers: &mut Headers,
+    ranges: &[RangeInclusive<u64>],
+    length: u64,
+) -> std::io::Result<Body>
+where
+    R: Read + Seek + Send + 'static,
+{
+    if let [range] = ranges {
+        source.seek(SeekFrom::Start(*range.start()))?;
+        let part_length = range.end() - range.start() + 1;
+        headers.insert(
+            "Content-Range",
+            format!("bytes {}-{}/{}", range.start(), range.end(), length),
+        );
+        return Ok(Body::Stream {
+            reader: Box::new(source.take(part_length)),
+            length: Some(part_length),
+        });
+    }
+
+    let boundary = boundary();
+    let content_type = headers.get("Content-Type").map(str::to_string);
+    headers.insert(
+        "Content-Type",
+        format!("multipart/byteranges; boundary={}", boundary),
+    );
+
+    // 📦 ส่วนหัวของแต่ละ part สลับกับช่วงข้อมูลจากไฟล์ แล้วปิดด้วย boundary ท้ายสุด
+    let mut parts = VecDeque::new();
+    for (i, range) in ranges.iter().enumerate() {
+        let mut head = format!("{}--{}\r\n", if i == 0 { "" } else { "\r\n" }, boundary);
+        if let Some(content_type) = &content_type {
+            head.push_str(&format!("Content-Type: {}\r\n", content_type));
+        }
+        head.push_str(&format!(
+            "Content-Range: bytes {}-{}/{}\r\n\r\n",
+            range.start(),
+            range.end(),
+            length
+        ));
+        parts.push_back(Part::Bytes(head.into_bytes()));
+        parts.push_back(Part::Range(range.clone()));
+    }
+    parts.push_back(Part::Bytes(
+        format!("\r\n--{}--\r\n", boundary).into_bytes(),
+    ));
+
+    let total = parts.iter().map(Part::len).sum();
+    Ok(Body::Stream {
+        reader: Box::new(Multipart {
+            source,
+            parts,
+            offset: 0,
+        }),
+        length: Some(total),
+    })
+}
+
+/// 🎲 boundary ที่ไม่ซ้ำกันระหว่าง response
+fn boundary() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("rust_server_{:016x}{:04x}", nanos, count & 0xffff)
+}
+
+enum Part {
+    Bytes(Vec<u8>),
+    Range(RangeInclusive<u64>),
+}
+
+impl Part {
+    fn len(&self) -> u64 {
+        match self {
+            Part::Bytes(bytes) => bytes.len() as u64,
+            Part::Range(range) => range.end() - range.start() + 1,
+        }
+    }
+}
+
+/// 📖 reader ของ `multipart/byteranges`: seek ไปยังแต่ละช่วงตอนเริ่มอ่าน part นั้น
+struct Multipart<R> {
+    source: R,
+    parts: VecDeque<Part>,
+    offset: u64, // 📍 ตำแหน่งใน part ปัจจุบัน
+}
+
+impl<R: Read + Seek> Read for Multipart<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let Some(part) = self.parts.front() else {
+                return Ok(0);
+            };
+            let remaining = (part.len() - self.offset).min(buf.len() as u64) as usize;
+            if remaining == 0 {
+                self.parts.pop_front();
+                self.offset = 0;
+                continue;
+            }
+
+            let read = match part {
+                Part::Bytes(bytes) => {
+                    let start = self.offset as usize;
+                    buf[..remaining].copy_from_slice(&bytes[start..start + remaining]);
+                    remaining
+                }
+                Part::Range(range) => {
+                    if self.offset == 0 {
+                        self.source.seek(SeekFrom::Start(*range.start()))?;
+                    }
+                    match self.source.read(&mut buf[..remaining])? {
+                        0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+                        read => read,
+                    }
+                }
+            };
+            self.offset += read as u64;
+            return Ok(read);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &str) -> Request {
+        let raw = format!("GET /log.txt HTTP/1.1\r\n{}\r\n", headers);
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    fn log() -> Response {
+        Response::builder(HttpStatus::Ok)
+            .etag("\"v1\"")
+            .text("0123456789")
+            .build()
+    }
+
+    fn sent(response: Response) -> String {
+        let mut out = Vec::new();
+        response.send(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn parses_single_suffix_and_open_ranges() {
+        assert_eq!(parse_ranges("bytes=0-4", 10), Some(vec![0..=4]));
+        assert_eq!(parse_ranges("bytes=-3", 10), Some(vec![7..=9]));
+        assert_eq!(parse_ranges("bytes=8-", 10), Some(vec![8..=9]));
+        assert_eq!(parse_ranges("bytes=5-100", 10), Some(vec![5..=9]));
+        assert_eq!(parse_ranges("bytes=20-30", 10), Some(vec![]));
+        assert_eq!(parse_ranges("bytes=5-1", 10), None);
+        assert_eq!(parse_ranges("items=0-1", 10), None);
+    }
+
+    #[test]
+    fn single_range_returns_206_with_content_range() {
+        let out = sent(apply(&request("Range: bytes=2-5\r\n"), log()));
+
+        assert!(out.starts_with("HTTP/1.1 206 Partial Content\r\n"));
+        assert!(out.contains("\r\nContent-Range: bytes 2-5/10\r\n"));
+        assert!(out.contains("\r\nContent-Length: 4\r\n"));
+        assert!(out.contains("\r\nAccept-Ranges: bytes\r\n"));
+        assert!(out.ends_with("\r\n\r\n2345"));
+    }
+
+    #[test]
+    fn multiple_ranges_return_multipart_byteranges() {
+        let response = apply(&request("Range: bytes=0-1, -2\r\n"), log());
+        let boundary = response.headers().get("Content-Type").unwrap()
+            ["multipart/byteranges; boundary=".len()..]
+            .to_string();
+        let out = sent(response);
+
+        let expected = format!(
+            "--{b}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Range: bytes 0-1/10\r\n\r\n01\r\n\
+             --{b}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Range: bytes 8-9/10\r\n\r\n89\r\n\
+             --{b}--\r\n",
+            b = boundary
+        );
+        assert!(out.ends_with(&format!("\r\n\r\n{}", expected)));
+        assert!(out.contains(&format!("\r\nContent-Length: {}\r\n", expected.len())));
+    }
+
+    #[test]
+    fn unsatisfiable_range_returns_416() {
+        let response = apply(&request("Range: bytes=50-60\r\n"), log());
+
+        assert_eq!(response.status(), &HttpStatus::RangeNotSatisfiable);
+        assert_eq!(response.headers().get("Content-Range"), Some("bytes */10"));
+    }
+
+    #[test]
+    fn stale_if_range_sends_full_body() {
+        let fresh = apply(&request("Range: bytes=0-1\r\nIf-Range: \"v1\"\r\n"), log());
+        let stale = apply(&request("Range: bytes=0-1\r\nIf-Range: \"v0\"\r\n"), log());
+
+        assert_eq!(fresh.status(), &HttpStatus::PartialContent);
+        assert_eq!(stale.status(), &HttpStatus::Ok);
+        assert_eq!(stale.body().len(), Some(10));
+    }
+}
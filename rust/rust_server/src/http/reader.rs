@@ -0,0 +1,244 @@
+use crate::http::{Error, Request, Result, body};
+use std::io::{BufRead, ErrorKind, Read};
+
+/// 📏 ขนาดที่อ่านจาก stream ต่อครั้ง
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// 🚧 ขีดจำกัดของ request ที่ reader ยอมรับ
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub max_header_size: usize, // 📋 ขนาดรวมของ request line + headers (เกินแล้วตอบ 431)
+    pub max_line_length: usize, // 📏 ความยาวสูงสุดต่อบรรทัด (เกินแล้วตอบ 431)
+    pub max_body_size: usize,   // 📦 ขนาด body สูงสุด (เกินแล้วตอบ 413)
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_header_size: 16 * 1024,
+            max_line_length: 8 * 1024,
+            max_body_size: 1024 * 1024,
+        }
+    }
+}
+
+/// 📖 HTTP/1.1 request reader แบบ incremental
+///
+/// อ่านจาก stream ทีละส่วนลงใน buffer ที่ขยายได้ จนกว่าจะเจอ header terminator
+/// (`\r\n\r\n`) แล้วจึง parse เป็น `Request` และอ่าน body ต่อจาก buffer เดิม
+/// ข้อมูลที่อ่านเกินมา (เช่น request ถัดไป) จะค้างอยู่ใน buffer ไม่หายไป
+pub struct RequestReader<R> {
+    inner: R,
+    buffer: Vec<u8>,
+    limits: Limits,
+    mid_request: bool, // 📨 ได้ byte แรกของ request ที่กำลังอ่านแล้ว
+}
+
+impl<R: Read> RequestReader<R> {
+    pub fn new(inner: R, limits: Limits) -> Self {
+        Self {
+            inner,
+            buffer: Vec::with_capacity(READ_CHUNK_SIZE),
+            limits,
+            mid_request: false,
+        }
+    }
+
+    /// 📨 อ่าน request ปัจจุบันมาได้บางส่วนแล้วหรือไม่
+    ///
+    /// ใช้แยก `Error::Timeout` ตอนว่างรอ request ถัดไป (ปิดเงียบๆ ได้)
+    /// ออกจาก client ที่หยุดส่งกลาง request (ควรตอบ 408)
+    pub fn is_mid_request(&self) -> bool {
+        self.mid_request
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// ✍️ stream ข้างใน (ใช้เขียน response กลับบน connection เดียวกัน)
+    ///
+    /// ข้อมูลที่อ่านมาค้างใน buffer ไม่หาย แต่ไม่ควรอ่านจาก stream นี้ตรงๆ
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// 📝 อ่าน request ถัดไปจาก stream
+    ///
+    /// # Returns
+    /// * `Ok(Some(request))` - อ่านได้ครบทั้ง head และ body
+    /// * `Ok(None)` - stream ปิดก่อนจะเริ่ม request ใหม่
+    /// * `Err` - request ไม่ถูกต้อง, เกิน limits หรือ I/O error
+    pub fn read_request(&mut self) -> Result<Option<Request>> {
+        let head_end = match self.read_head()? {
+            Some(head_end) => head_end,
+            None => return Ok(None),
+        };
+
+        let mut request = Request::try_from(&self.buffer[..head_end])?;
+        self.buffer.drain(..head_end);
+
+        let limits = self.limits;
+        let body = body::read_body(request.headers(), &mut *self, &limits)?;
+        request.set_body(body);
+        self.mid_request = false;
+
+        Ok(Some(request))
+    }
+
+    /// 🔎 อ่านจนกว่าจะเจอ `\r\n\r\n` แล้วคืนตำแหน่งสิ้นสุดของ head
+    fn read_head(&mut self) -> Result<Option<usize>> {
+        let mut scanned = 0;
+
+        loop {
+            // RFC 9112: ควรข้ามบรรทัดว่างที่นำหน้า request line
+            while self.buffer.starts_with(b"\r\n") {
+                self.buffer.drain(..2);
+            }
+            self.mid_request |= !self.buffer.is_empty();
+
+            if let Some(end) = find_head_end(&self.buffer, scanned) {
+                self.check_limits(&self.buffer[..end])?;
+                return Ok(Some(end));
+            }
+            self.check_limits(&self.buffer)?;
+
+            scanned = self.buffer.len().saturating_sub(3);
+            if self.fill()? == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                return Err(Error::InvalidRequest);
+            }
+        }
+    }
+
+    fn check_limits(&self, head: &[u8]) -> Result<()> {
+        if head.len() > self.limits.max_header_size {
+            return Err(Error::HeaderTooLarge);
+        }
+
+        let too_long = head
+            .split(|&b| b == b'\n')
+            .any(|line| line.len() > self.limits.max_line_length);
+        if too_long {
+            return Err(Error::HeaderTooLarge);
+        }
+
+        Ok(())
+    }
+
+    /// 📥 อ่านข้อมูลจาก stream ต่อท้าย buffer คืนจำนวน bytes ที่อ่านได้
+    fn fill(&mut self) -> std::io::Result<usize> {
+        let mut chunk = [0; READ_CHUNK_SIZE];
+
+        loop {
+            match self.inner.read(&mut chunk) {
+                Ok(read) => {
+                    self.buffer.extend_from_slice(&chunk[..read]);
+                    return Ok(read);
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<R: Read> Read for RequestReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.buffer.is_empty() {
+            return self.inner.read(buf);
+        }
+
+        let read = self.buffer.len().min(buf.len());
+        buf[..read].copy_from_slice(&self.buffer[..read]);
+        self.buffer.drain(..read);
+        Ok(read)
+    }
+}
+
+impl<R: Read> BufRead for RequestReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        if self.buffer.is_empty() {
+            self.fill()?;
+        }
+        Ok(&self.buffer)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.buffer.drain(..amount);
+    }
+}
+
+fn find_head_end(buffer: &[u8], from: usize) -> Option<usize> {
+    buffer[from..]
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|i| from + i + 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 🧩 จำลอง TCP stream ที่ส่งข้อมูลมาทีละ segment
+    struct Segmented(Vec<&'static [u8]>);
+
+    impl Read for Segmented {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() {
+                return Ok(0);
+            }
+            let segment = self.0.remove(0);
+            buf[..segment.len()].copy_from_slice(segment);
+            Ok(segment.len())
+        }
+    }
+
+    #[test]
+    fn reads_request_split_across_segments() {
+        let stream = Segmented(vec![
+            b"POST /submit HT",
+            b"TP/1.1\r\nContent-Le",
+            b"ngth: 11\r\n\r\nhello ",
+            b"world",
+        ]);
+        let mut reader = RequestReader::new(stream, Limits::default());
+
+        let request = reader.read_request().unwrap().unwrap();
+        assert_eq!(request.path(), "/submit");
+        assert_eq!(request.body(), b"hello world");
+        assert!(reader.read_request().unwrap().is_none());
+    }
+
+    #[test]
+    fn reads_request_larger_than_one_chunk() {
+        let header = format!("X-Padding: {}\r\n", "a".repeat(READ_CHUNK_SIZE));
+        let raw = format!("GET / HTTP/1.1\r\n{}\r\n", header);
+        let mut reader = RequestReader::new(raw.as_bytes(), Limits::default());
+
+        let request = reader.read_request().unwrap().unwrap();
+        assert_eq!(
+            request.headers().get("x-padding").map(str::len),
+            Some(READ_CHUNK_SIZE)
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_head_and_lines() {
+        let limits = Limits {
+            max_header_size: 64,
+            max_line_length: 32,
+            ..Limits::default()
+        };
+        let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(40));
+        let many_lines = format!("GET / HTTP/1.1\r\n{}\r\n", "X-A: b\r\n".repeat(10));
+
+        let mut reader = RequestReader::new(long_line.as_bytes(), limits);
+        assert!(matches!(reader.read_request(), Err(Error::HeaderTooLarge)));
+
+        let mut reader = RequestReader::new(many_lines.as_bytes(), limits);
+        assert!(matches!(reader.read_request(), Err(Error::HeaderTooLarge)));
+    }
+}
@@ -1,14 +1,21 @@
 use crate::http::Error;
 use crate::http::Result;
-use crate::http::{Method, QueryString};
+use crate::http::{CookieJar, Headers, Method, QueryString, headers, query_string};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Request {
     method: Method,
     path: String,
+    query: Option<String>, // 🔍 query string ดิบหลัง `?` (เช่นสำหรับ access log)
     query_string: Option<QueryString>,
+    headers: Headers,
+    cookies: CookieJar, // 🍪 จาก header `Cookie`
+    body: Vec<u8>,
+    params: HashMap<String, String>,
 }
 
 impl Request {
@@ -20,40 +27,303 @@ impl Request {
         &self.path
     }
 
+    /// 🔍 query string ดิบหลัง `?` (ยังไม่ percent-decode)
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
     pub fn query_string(&self) -> Option<&QueryString> {
         self.query_string.as_ref()
     }
+
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut Headers {
+        &mut self.headers
+    }
+
+    /// 🍪 cookie ทั้งหมดที่ client ส่งมา
+    pub fn cookies(&self) -> &CookieJar {
+        &self.cookies
+    }
+
+    /// 🍪 ค่าของ cookie `name` (percent-decode แล้ว)
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name)
+    }
+
+    /// 🔁 client ต้องการใช้ connection ต่อหรือไม่ (HTTP/1.1 เป็น keep-alive โดย default)
+    pub fn keep_alive(&self) -> bool {
+        !self.headers.contains_token("Connection", "close")
+    }
+
+    /// 📦 body แบบ raw bytes
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// 📝 body แบบ UTF-8 text
+    pub fn text(&self) -> Result<&str> {
+        Ok(str::from_utf8(&self.body)?)
+    }
+
+    /// 🧾 body แบบ `application/x-www-form-urlencoded` (key=value&key=value)
+    pub fn form(&self) -> Result<QueryString> {
+        Ok(QueryString::from(self.text()?))
+    }
+
+    pub fn set_body(&mut self, body: Vec<u8>) {
+        self.body = body;
+    }
+
+    /// 🧷 path parameter ที่ `Router` จับได้ เช่น `:id` ใน `/users/:id`
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+
+    pub fn params(&self) -> &HashMap<String, String> {
+        &self.params
+    }
+
+    pub fn set_params(&mut self, params: HashMap<String, String>) {
+        self.params = params;
+    }
+
+    /// 🪶 สำเนาที่มีแค่ส่วนหัว (ไม่ copy body) ไว้ใช้หลังส่ง request ตัวจริงให้ handler
+    #[cfg(feature = "tokio")]
+    pub(crate) fn head(&self) -> Self {
+        Self {
+            method: self.method.clone(),
+            path: self.path.clone(),
+            query: self.query.clone(),
+            query_string: self.query_string.clone(),
+            headers: self.headers.clone(),
+            cookies: self.cookies.clone(),
+            body: Vec::new(),
+            params: HashMap::new(),
+        }
+    }
 }
 
 impl TryFrom<&[u8]> for Request {
     type Error = Error;
 
+    /// 📝 parse แบบยืม (`RequestRef`) แล้วค่อย copy เป็น owned ครั้งเดียว
     fn try_from(buf: &[u8]) -> Result<Self> {
-        let request = str::from_utf8(&buf)?;
+        RequestRef::try_from(buf).map(RequestRef::into_owned)
+    }
+}
+
+/// 🪶 RequestRef: request head ที่ยืม path, headers และ query string จาก buffer ที่อ่านมา
+/// (zero-copy) ใช้เมื่อไม่ต้องเก็บ request ไว้นานกว่า buffer
+///
+/// ต้องการ `Request` แบบ owned (เช่น ส่งต่อให้ `Handler`) ใช้ `into_owned()`
+///
+/// ```
+/// use rust_server::http::RequestRef;
+///
+/// let raw = b"GET /search?q=rust+lang HTTP/1.1\r\nHost: localhost\r\n\r\n";
+/// let request = RequestRef::try_from(&raw[..]).unwrap();
+///
+/// assert_eq!(request.path(), "/search");
+/// assert_eq!(request.header("host"), Some("localhost"));
+/// assert_eq!(request.query_param("q").as_deref(), Some("rust lang"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestRef<'buf> {
+    method: Method,
+    path: &'buf str,
+    query: Option<&'buf str>,             // 🔍 query string ดิบ (ยังไม่ decode)
+    headers: Vec<(&'buf str, &'buf str)>, // 📋 allocation เดียวต่อ request
+}
+
+impl<'buf> RequestRef<'buf> {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn path(&self) -> &'buf str {
+        self.path
+    }
+
+    /// 🔍 query string ดิบหลัง `?` (ยังไม่ percent-decode)
+    pub fn query(&self) -> Option<&'buf str> {
+        self.query
+    }
+
+    /// 🧩 คู่ key/value ของ query string ที่ decode แล้ว
+    /// (allocate เฉพาะค่าที่มี `%` หรือ `+` ต้อง decode)
+    pub fn query_pairs(&self) -> impl Iterator<Item = (Cow<'buf, str>, Cow<'buf, str>)> {
+        self.query.into_iter().flat_map(query_string::parse_pairs)
+    }
+
+    /// 🔍 ค่าแรกของ query parameter `key`
+    pub fn query_param(&self, key: &str) -> Option<Cow<'buf, str>> {
+        self.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// ค่าแรกของ header `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&'buf str> {
+        self.header_all(name).next()
+    }
+
+    /// ทุกค่าของ header `name` ตามลำดับที่ได้รับ
+    pub fn header_all(&self, name: &str) -> impl Iterator<Item = &'buf str> {
+        self.headers
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    pub fn headers(&self) -> impl Iterator<Item = (&'buf str, &'buf str)> {
+        self.headers.iter().copied()
+    }
+
+    /// 🔁 client ต้องการใช้ connection ต่อหรือไม่ (ดู `Request::keep_alive`)
+    pub fn keep_alive(&self) -> bool {
+        !self
+            .header_all("Connection")
+            .flat_map(|value| value.split(','))
+            .any(|item| item.trim().eq_ignore_ascii_case("close"))
+    }
 
-        let mut request = request.split_whitespace();
-        let method = request.next().ok_or(Error::InvalidRequest)?;
-        let mut path = request.next().ok_or(Error::InvalidRequest)?;
-        let protocol = request.next().ok_or(Error::InvalidRequest)?;
+    /// 📦 copy ทุกอย่างออกจาก buffer เป็น `Request` แบบ owned (body ว่าง)
+    pub fn into_owned(self) -> Request {
+        let mut headers = Headers::new();
+        for (name, value) in self.headers {
+            headers.append(name, value);
+        }
+
+        Request {
+            method: self.method,
+            path: self.path.to_string(),
+            query: self.query.map(str::to_string),
+            query_string: self.query.map(QueryString::from),
+            cookies: CookieJar::from(&headers),
+            headers,
+            body: Vec::new(),
+            params: HashMap::new(),
+        }
+    }
+}
+
+impl<'buf> TryFrom<&'buf [u8]> for RequestRef<'buf> {
+    type Error = Error;
+
+    fn try_from(buf: &'buf [u8]) -> Result<Self> {
+        let request = str::from_utf8(buf)?;
+
+        // 📝 request line คือบรรทัดแรก ที่เหลือคือ header block จนถึงบรรทัดว่าง
+        // body (ถ้ามี) อ่านแยกด้วย `body::read_body` แล้วใส่ผ่าน `set_body`
+        let (request_line, header_block) =
+            request.split_once("\r\n").ok_or(Error::InvalidRequest)?;
+
+        let mut request_line = request_line.split_whitespace();
+        let method = request_line.next().ok_or(Error::InvalidRequest)?;
+        let target = request_line.next().ok_or(Error::InvalidRequest)?;
+        let protocol = request_line.next().ok_or(Error::InvalidRequest)?;
+        // 🚫 request line มีได้แค่ 3 ส่วน (`GET / HTTP/1.1 garbage` ไม่ผ่าน)
+        if request_line.next().is_some() {
+            return Err(Error::InvalidRequest);
+        }
 
+        // 🏷️ HTTP version อื่น (เช่น HTTP/1.0, HTTP/2.0) ตอบ 505 ส่วน token แปลกๆ ตอบ 400
         if protocol != "HTTP/1.1" {
-            return Err(Error::InvalidProtocol);
+            if protocol.starts_with("HTTP/") {
+                return Err(Error::InvalidProtocol);
+            }
+            return Err(Error::InvalidRequest);
         }
 
         let method: Method = method.parse()?;
-        let query_string = match path.find('?') {
-            Some(i) => {
-                let qs = &path[i + 1..];
-                path = &path[..i];
-                Some(QueryString::from(qs))
-            }
-            None => None,
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (target, None),
         };
 
+        let mut headers = Vec::new();
+        for line in header_block.split("\r\n") {
+            if line.is_empty() {
+                break;
+            }
+            headers.push(headers::parse_line(line)?);
+        }
+
         Ok(Self {
-            method: method,
-            path: path.to_string(),
-            query_string: query_string,
+            method,
+            path,
+            query,
+            headers,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_line_and_headers() {
+        let raw = b"GET /hello?name=petch HTTP/1.1\r\nHost: 127.0.0.1:8000\r\nUser-Agent: curl/8.0\r\n\r\n";
+        let request = Request::try_from(&raw[..]).unwrap();
+
+        assert_eq!(request.path(), "/hello");
+        assert_eq!(request.headers().get("host"), Some("127.0.0.1:8000"));
+        assert_eq!(request.headers().get("user-agent"), Some("curl/8.0"));
+    }
+
+    #[test]
+    fn borrowed_request_points_into_buffer() {
+        let raw = b"GET /items?tag=a&tag=b%20c HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let request = RequestRef::try_from(&raw[..]).unwrap();
+
+        let path = request.path();
+        assert!(raw.as_ptr_range().contains(&path.as_ptr()));
+        assert!(matches!(
+            request.query_param("tag"),
+            Some(Cow::Borrowed("a"))
+        ));
+        assert_eq!(
+            request.query_pairs().map(|(_, v)| v).collect::<Vec<_>>(),
+            ["a", "b c"]
+        );
+
+        let owned = request.into_owned();
+        assert_eq!(owned.path(), "/items");
+        assert_eq!(owned.headers().get("host"), Some("localhost"));
+        assert_eq!(owned.query_string().unwrap().get("tag"), Some("a"));
+    }
+
+    #[test]
+    fn distinguishes_unsupported_version_from_garbage() {
+        let http10 = Request::try_from(&b"GET / HTTP/1.0\r\n\r\n"[..]);
+        let garbage = Request::try_from(&b"GET / FOO\r\n\r\n"[..]);
+
+        assert!(matches!(http10, Err(Error::InvalidProtocol)));
+        assert!(matches!(garbage, Err(Error::InvalidRequest)));
+
+        let extra = b"GET / HTTP/1.1 garbage\r\n\r\n";
+        assert!(matches!(
+            Request::try_from(&extra[..]),
+            Err(Error::InvalidRequest)
+        ));
+        assert!(matches!(
+            RequestRef::try_from(&extra[..]),
+            Err(Error::InvalidRequest)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_header_line() {
+        let raw = b"GET / HTTP/1.1\r\nHost 127.0.0.1\r\n\r\n";
+
+        assert!(matches!(
+            Request::try_from(&raw[..]),
+            Err(Error::InvalidHeader(line)) if line == "Host 127.0.0.1"
+        ));
+    }
+}
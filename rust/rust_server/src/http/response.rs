@@ -1,23 +1,482 @@
-use std::net::TcpStream;
+use crate::http::headers::is_token;
+use crate::http::server::Connection;
+use crate::http::{CacheControl, Cookie, Headers, HttpStatus};
+use chrono::{DateTime, Utc};
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::time::SystemTime;
 
-use crate::http::HttpStatus;
-use std::io::Write;
+/// 🏷️ ค่า `Server` header เริ่มต้น เช่น `rust_server/0.1.0`
+const SERVER_NAME: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
+/// 📏 ขนาด chunk ตอนส่ง body แบบ stream
+const STREAM_CHUNK_SIZE: usize = 8 * 1024;
+
+/// 📦 body ของ response
+pub enum Body {
+    Empty,
+    Bytes(Vec<u8>),
+    /// 🌊 อ่านจาก reader ตอนส่ง: รู้ความยาวส่งด้วย `Content-Length` ไม่รู้ส่งแบบ chunked
+    Stream {
+        reader: Box<dyn Read + Send>,
+        length: Option<u64>,
+    },
+    /// 📄 ไฟล์ `length` bytes แรก: seek ได้จึงตอบ Range request ได้โดยไม่ต้องอ่านทั้งไฟล์
+    File {
+        file: File,
+        length: u64,
+    },
+}
+
+impl Body {
+    /// ความยาว body ถ้ารู้ล่วงหน้า
+    pub fn len(&self) -> Option<u64> {
+        match self {
+            Body::Empty => Some(0),
+            Body::Bytes(bytes) => Some(bytes.len() as u64),
+            Body::Stream { length, .. } => *length,
+            Body::File { length, .. } => Some(*length),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+}
+
+impl Debug for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Body::Empty => write!(f, "Empty"),
+            Body::Bytes(bytes) => write!(f, "Bytes({} bytes)", bytes.len()),
+            Body::Stream { length, .. } => write!(f, "Stream(length: {:?})", length),
+            Body::File { length, .. } => write!(f, "File({} bytes)", length),
+        }
+    }
+}
+
+/// 🔀 งานที่ server ทำต่อบน connection เดิมหลังส่ง `101 Switching Protocols`
+pub(crate) struct OnUpgrade(Box<dyn FnOnce(Box<dyn Connection>) + Send>);
+
+impl OnUpgrade {
+    pub(crate) fn run(self, connection: Box<dyn Connection>) {
+        (self.0)(connection)
+    }
+}
+
+impl Debug for OnUpgrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OnUpgrade")
+    }
+}
+
+#[derive(Debug)]
 pub struct Response {
     http_status: HttpStatus,
-    body: Option<String>,
+    headers: Headers,
+    body: Body,
+    head_only: bool,            // 🙈 ตอบ `HEAD`: ส่ง headers เหมือน GET แต่ไม่ส่ง body
+    upgrade: Option<OnUpgrade>, // 🔀 เปลี่ยน protocol หลังส่ง 101 (เช่น WebSocket)
 }
 
 impl Response {
+    /// 🏗️ สร้าง response แบบ text (ใช้ `Response::builder` สำหรับ binary/stream body)
     pub fn new(http_status: HttpStatus, body: Option<String>) -> Self {
-        Self { http_status, body }
+        let builder = Response::builder(http_status);
+        match body {
+            Some(body) => builder.text(body).build(),
+            None => builder.build(),
+        }
+    }
+
+    /// 🧱 เริ่มสร้าง response ทีละส่วน
+    ///
+    /// ```
+    /// use rust_server::http::{HttpStatus, Response};
+    ///
+    /// let response = Response::builder(HttpStatus::Ok)
+    ///     .header("Content-Type", "application/octet-stream")
+    ///     .body(vec![0xca, 0xfe])
+    ///     .build();
+    /// ```
+    pub fn builder(http_status: HttpStatus) -> ResponseBuilder {
+        ResponseBuilder {
+            http_status,
+            headers: Headers::new(),
+            body: Body::Empty,
+        }
+    }
+
+    /// ➕ ตั้งค่า header (แทนที่ค่าเดิมถ้ามี)
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// 🗄️ ตั้ง `Cache-Control` (แทนที่ค่าเดิมถ้ามี)
+    pub fn with_cache_control(self, policy: &CacheControl) -> Self {
+        self.with_header("Cache-Control", policy.to_string())
+    }
+
+    /// 🍪 เพิ่ม `Set-Cookie` (ตั้งได้หลาย cookie ต่อ response)
+    pub fn with_cookie(mut self, cookie: &Cookie) -> Self {
+        self.headers.append("Set-Cookie", cookie.to_string());
+        self
+    }
+
+    pub fn status(&self) -> &HttpStatus {
+        &self.http_status
+    }
+
+    pub fn headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut Headers {
+        &mut self.headers
+    }
+
+    pub fn body(&self) -> &Body {
+        &self.body
+    }
+
+    /// 🧩 แยกเป็น status, headers และ body (เช่นเพื่อประกอบ response ใหม่จาก body เดิม)
+    pub fn into_parts(self) -> (HttpStatus, Headers, Body) {
+        (self.http_status, self.headers, self.body)
+    }
+
+    /// 🧩 ประกอบ response จาก status, headers และ body ตรงๆ
+    pub fn from_parts(http_status: HttpStatus, headers: Headers, body: Body) -> Self {
+        Self {
+            http_status,
+            headers,
+            body,
+            head_only: false,
+            upgrade: None,
+        }
+    }
+
+    /// 🔀 ให้ server เรียก `on_upgrade` กับ connection เดิมหลังส่ง response นี้ (ต้องเป็น 101)
+    pub(crate) fn with_upgrade(
+        mut self,
+        on_upgrade: impl FnOnce(Box<dyn Connection>) + Send + 'static,
+    ) -> Self {
+        self.upgrade = Some(OnUpgrade(Box::new(on_upgrade)));
+        self
+    }
+
+    /// 🔀 ดึงงานหลัง upgrade ออกมา (ถ้ามี)
+    pub(crate) fn take_upgrade(&mut self) -> Option<OnUpgrade> {
+        self.upgrade.take()
+    }
+
+    /// 🙈 ใช้ตอบ `HEAD`: headers (รวม `Content-Length`) เหมือนเดิมแต่ไม่ส่ง body
+    pub fn head_only(mut self) -> Self {
+        self.head_only = true;
+        self
+    }
+
+    /// 🔌 response นี้ขอปิด connection หลังส่งเสร็จหรือไม่ (`Connection: close`)
+    pub fn closes_connection(&self) -> bool {
+        self.headers.contains_token("Connection", "close")
+    }
+
+    /// 📤 เขียน response ลง `stream` ใดก็ได้ที่ implement `Write`
+    /// (`TcpStream`, `Vec<u8>` ใน test ฯลฯ)
+    ///
+    /// เติม `Date`, `Server` และ `Content-Length` (หรือ `Transfer-Encoding: chunked`
+    /// สำหรับ stream ที่ไม่รู้ความยาว) ให้อัตโนมัติ ถ้า handler ไม่ได้ตั้งไว้เอง
+    /// header ที่ชื่อไม่ใช่ token จะไม่ถูกส่ง และค่าถูกตัด CR, LF, NUL ออกก่อนเขียน
+    ///
+    /// # Returns
+    /// * จำนวน bytes ของ body ที่ส่งไป (ไม่รวม headers และ framing ของ chunked)
+    pub fn send<W: Write>(self, stream: &mut W) -> std::io::Result<u64> {
+        let Response {
+            http_status,
+            mut headers,
+            body,
+            head_only,
+            ..
+        } = self;
+
+        if !headers.contains("Date") {
+            headers.insert("Date", http_date());
+        }
+        if !headers.contains("Server") {
+            headers.insert("Server", SERVER_NAME);
+        }
+        // 🚫 1xx/204/304 ห้ามมี body: ไม่ส่ง body และไม่ใส่ Content-Length ให้
+        let body = if http_status.forbids_body() {
+            Body::Empty
+        } else {
+            match body.len() {
+                Some(length) => headers.insert("Content-Length", length.to_string()),
+                None => {
+                    headers.remove("Content-Length");
+                    headers.insert("Transfer-Encoding", "chunked");
+                }
+            }
+            body
+        };
+
+        // 📋 status line + headers เขียนรวดเดียว
+        let mut head = format!("HTTP/1.1 {}\r\n", http_status);
+        for (name, value) in headers.iter() {
+            // 🛡️ กัน header injection / response splitting จากค่าที่มาจาก request:
+            // ชื่อที่ไม่ใช่ token ไม่ส่ง และตัด CR, LF, NUL ออกจากค่า
+            if name.is_empty() || !name.bytes().all(is_token) {
+                eprintln!(
+                    "⚠️ Dropping response header with invalid name `{}`",
+                    name.escape_debug()
+                );
+                continue;
+            }
+            head.push_str(name);
+            head.push_str(": ");
+            head.extend(value.chars().filter(|c| !matches!(c, '\r' | '\n' | '\0')));
+            head.push_str("\r\n");
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+
+        if head_only {
+            stream.flush()?;
+            return Ok(0);
+        }
+
+        let sent = match body {
+            Body::Empty => 0,
+            Body::Bytes(bytes) => {
+                stream.write_all(&bytes)?;
+                bytes.len() as u64
+            }
+            Body::Stream {
+                reader,
+                length: Some(length),
+            } => write_sized(reader, length, stream)?,
+            Body::File { file, length } => write_sized(file, length, stream)?,
+            Body::Stream {
+                reader,
+                length: None,
+            } => write_chunked(reader, stream)?,
+        };
+
+        stream.flush()?;
+        Ok(sent)
+    }
+}
+
+/// 🧱 ResponseBuilder: ประกอบ status, headers และ body ก่อน `build()`
+pub struct ResponseBuilder {
+    http_status: HttpStatus,
+    headers: Headers,
+    body: Body,
+}
+
+impl ResponseBuilder {
+    /// ➕ เพิ่ม header (ชื่อซ้ำได้ เช่น `Set-Cookie` หลายค่า)
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// 🍪 เพิ่ม `Set-Cookie`
+    pub fn cookie(self, cookie: &Cookie) -> Self {
+        self.header("Set-Cookie", cookie.to_string())
+    }
+
+    /// 📦 body แบบ bytes (binary ได้)
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = Body::Bytes(body.into());
+        self
+    }
+
+    /// 📝 body แบบ text พร้อม `Content-Type: text/plain; charset=utf-8`
+    /// (ถ้ายังไม่ได้ตั้ง Content-Type ไว้)
+    pub fn text(mut self, body: impl Into<String>) -> Self {
+        if !self.headers.contains("Content-Type") {
+            self.headers
+                .insert("Content-Type", "text/plain; charset=utf-8");
+        }
+        self.body = Body::Bytes(body.into().into_bytes());
+        self
+    }
+
+    /// 🏷️ `ETag` ของ representation นี้ (เช่นจาก `cache::etag_for_bytes`)
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.headers.insert("ETag", etag);
+        self
+    }
+
+    /// 🕰️ `Last-Modified` ใช้ตอบ `If-Modified-Since` ด้วย 304
+    pub fn last_modified(mut self, modified: SystemTime) -> Self {
+        self.headers
+            .insert("Last-Modified", format_http_date(modified));
+        self
+    }
+
+    /// 🗄️ นโยบาย `Cache-Control` ของ response นี้
+    pub fn cache_control(mut self, policy: &CacheControl) -> Self {
+        self.headers.insert("Cache-Control", policy.to_string());
+        self
+    }
+
+    /// 🌊 body ที่อ่านจาก reader ตอนส่ง (เช่นไฟล์ใหญ่)
+    ///
+    /// # Arguments
+    /// * `length` - ความยาวถ้ารู้ (`Content-Length`) หรือ `None` เพื่อส่งแบบ chunked
+    pub fn stream(mut self, reader: impl Read + Send + 'static, length: Option<u64>) -> Self {
+        self.body = Body::Stream {
+            reader: Box::new(reader),
+            length,
+        };
+        self
+    }
+
+    /// 📄 body จากไฟล์ `length` bytes (ตอบ Range request ได้ด้วยการ seek)
+    pub fn file(mut self, file: File, length: u64) -> Self {
+        self.body = Body::File { file, length };
+        self
+    }
+
+    pub fn build(self) -> Response {
+        Response::from_parts(self.http_status, self.headers, self.body)
+    }
+}
+
+/// 🕰️ เวลาปัจจุบันในรูปแบบ HTTP-date (RFC 9110) เช่น `Sun, 06 Nov 1994 08:49:37 GMT`
+pub fn http_date() -> String {
+    format_http_date(SystemTime::now())
+}
+
+/// 🕰️ `SystemTime` ในรูปแบบ HTTP-date (ใช้กับ `Last-Modified`, `Expires` ฯลฯ)
+pub fn format_http_date(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+/// 📏 ส่ง `length` bytes จาก reader (สั้นกว่าที่ประกาศไว้ถือเป็น error)
+fn write_sized<W: Write>(reader: impl Read, length: u64, stream: &mut W) -> std::io::Result<u64> {
+    let copied = std::io::copy(&mut reader.take(length), stream)?;
+    if copied < length {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "stream body shorter than its declared length",
+        ));
     }
+    Ok(copied)
+}
 
-    pub fn send(&self, stream: &mut TcpStream) -> std::io::Result<()> {
-        let body = match &self.body {
-            Some(v) => v,
-            None => "",
+fn write_chunked<W: Write>(
+    mut reader: Box<dyn Read + Send>,
+    stream: &mut W,
+) -> std::io::Result<u64> {
+    let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+    let mut sent = 0;
+
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
         };
-        write!(stream, "HTTP/1.1 {}\r\n\r\n{}", self.http_status, body)
+        write!(stream, "{:X}\r\n", read)?;
+        stream.write_all(&chunk[..read])?;
+        stream.write_all(b"\r\n")?;
+        // 🚿 ส่งออกทีละ chunk: stream ที่ผลิตข้อมูลช้าๆ (เช่น SSE) client จะเห็นทันที
+        stream.flush()?;
+        sent += read as u64;
+    }
+
+    stream.write_all(b"0\r\n\r\n")?;
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sent(response: Response) -> String {
+        let mut out = Vec::new();
+        response.send(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn adds_content_length_date_and_server() {
+        let out = sent(Response::new(HttpStatus::Ok, Some("home".to_string())));
+
+        assert!(out.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(out.contains("\r\nContent-Type: text/plain; charset=utf-8\r\n"));
+        assert!(out.contains("\r\nContent-Length: 4\r\n"));
+        assert!(out.contains("\r\nDate: "));
+        assert!(out.contains(&format!("\r\nServer: {}\r\n", SERVER_NAME)));
+        assert!(out.ends_with("\r\n\r\nhome"));
+    }
+
+    #[test]
+    fn strips_line_breaks_and_drops_invalid_header_names() {
+        let out = sent(
+            Response::builder(HttpStatus::Found)
+                .header("Location", "/next\r\nSet-Cookie: admin=1\r\n\r\n<h1>")
+                .header("Bad\r\nName", "x")
+                .header("X Space", "y")
+                .build(),
+        );
+
+        assert!(out.contains("\r\nLocation: /nextSet-Cookie: admin=1<h1>\r\n"));
+        assert!(!out.contains("\r\nSet-Cookie:"));
+        assert!(!out.contains("Name: x") && !out.contains("X Space"));
+    }
+
+    #[test]
+    fn keeps_headers_set_by_handler() {
+        let response = Response::builder(HttpStatus::Ok)
+            .header("Server", "custom")
+            .body(vec![0, 159, 146, 150])
+            .build();
+        let mut out = Vec::new();
+        response.send(&mut out).unwrap();
+
+        let text = String::from_utf8_lossy(&out);
+        assert!(text.contains("\r\nServer: custom\r\n"));
+        assert!(!text.contains(SERVER_NAME));
+        assert!(out.ends_with(&[b'\r', b'\n', 0, 159, 146, 150]));
+    }
+
+    #[test]
+    fn omits_body_for_no_content() {
+        let out = sent(Response::new(
+            HttpStatus::NoContent,
+            Some("ignored".to_string()),
+        ));
+
+        assert!(out.starts_with("HTTP/1.1 204 No Content\r\n"));
+        assert!(!out.contains("Content-Length"));
+        assert!(out.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn head_only_keeps_content_length_but_drops_body() {
+        let response = Response::new(HttpStatus::Ok, Some("home".to_string())).head_only();
+        let out = sent(response);
+
+        assert!(out.contains("\r\nContent-Length: 4\r\n"));
+        assert!(out.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn streams_unknown_length_body_as_chunked() {
+        let response = Response::builder(HttpStatus::Ok)
+            .stream(&b"hello world"[..], None)
+            .build();
+        let out = sent(response);
+
+        assert!(out.contains("\r\nTransfer-Encoding: chunked\r\n"));
+        assert!(!out.contains("Content-Length"));
+        assert!(out.ends_with("\r\n\r\nB\r\nhello world\r\n0\r\n\r\n"));
     }
 }
@@ -0,0 +1,462 @@
+use crate::http::query_string::percent_decode;
+use crate::http::{CacheControl, Handler, HttpStatus, Method, Request, Response};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 🎯 handler ของแต่ละ route: รับ request แล้วคืน response
+pub type RouteHandler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// 🧩 ส่วนหนึ่งของ path pattern
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Static(String),   // `/users`
+    Param(String),    // `/:id` จับได้ 1 segment
+    Wildcard(String), // `/*rest` จับ segment ที่เหลือทั้งหมด (ต้องอยู่ท้ายสุด)
+}
+
+#[derive(Clone)]
+struct Route {
+    method: Method,
+    pattern: Vec<Segment>,
+    handler: Arc<RouteHandler>,
+    cache_control: Option<String>, // 🗄️ ใส่ให้ response ที่ handler ไม่ได้ตั้ง `Cache-Control` เอง
+}
+
+impl Route {
+    /// 🎯 เรียก handler แล้วเติม `Cache-Control` ของ route ถ้า handler ไม่ได้ตั้งไว้
+    fn call(&self, request: &Request) -> Response {
+        let mut response = (self.handler)(request);
+        if let Some(policy) = &self.cache_control
+            && !response.headers().contains("Cache-Control")
+        {
+            response
+                .headers_mut()
+                .insert("Cache-Control", policy.as_str());
+        }
+        response
+    }
+}
+
+/// 🗺️ Router: จับคู่ `Method` + path pattern ไปยัง handler
+///
+/// ```
+/// use rust_server::http::{HttpStatus, Response, Router};
+///
+/// let router = Router::new()
+///     .get("/", |_| Response::new(HttpStatus::Ok, Some("home".to_string())))
+///     .get("/users/:id", |req| {
+///         let id = req.param("id").unwrap_or_default();
+///         Response::new(HttpStatus::Ok, Some(format!("user {}", id)))
+///     });
+/// ```
+///
+/// ถ้าหลาย route ตรงกับ path เดียวกัน route ที่เจาะจงกว่าชนะ โดยเทียบทีละ segment
+/// (static > `:param` > `*wildcard`) เช่น `/users/me` ชนะ `/users/:id` เสมอ
+/// ไม่ว่าจะลงทะเบียนก่อนหรือหลัง ถ้าเจาะจงเท่ากันใช้ route ที่ลงทะเบียนก่อน
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ➕ ลงทะเบียน handler สำหรับ `method` และ `pattern`
+    ///
+    /// # Arguments
+    /// * `pattern` - path เช่น `/users/:id` หรือ `/static/*rest`
+    ///   (ค่าที่ `:id`/`*rest` จับได้ถูก percent-decode แล้ว)
+    pub fn route<F>(mut self, method: Method, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.routes.push(Route {
+            method,
+            pattern: parse_pattern(pattern),
+            handler: Arc::new(handler),
+            cache_control: None,
+        });
+        self
+    }
+
+    /// 🗄️ กำหนด `Cache-Control` ให้ route ที่เพิ่งลงทะเบียนล่าสุด
+    ///
+    /// ```
+    /// use rust_server::http::{CacheControl, HttpStatus, Response, Router};
+    /// use std::time::Duration;
+    ///
+    /// let router = Router::new()
+    ///     .get("/news", |_| Response::new(HttpStatus::Ok, Some("news".to_string())))
+    ///     .cache_control(CacheControl::new().public().max_age(Duration::from_secs(60)))
+    ///     .get("/me", |_| Response::new(HttpStatus::Ok, Some("me".to_string())))
+    ///     .cache_control(CacheControl::new().private().no_store());
+    /// ```
+    pub fn cache_control(mut self, policy: CacheControl) -> Self {
+        if let Some(route) = self.routes.last_mut() {
+            route.cache_control = Some(policy.to_string());
+        }
+        self
+    }
+
+    pub fn get<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::GET, pattern, handler)
+    }
+
+    pub fn post<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::POST, pattern, handler)
+    }
+
+    pub fn put<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::PUT, pattern, handler)
+    }
+
+    pub fn delete<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::DELETE, pattern, handler)
+    }
+
+    pub fn patch<F>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::PATCH, pattern, handler)
+    }
+
+    /// 🎣 Dispatch request ไปยัง route ที่ตรงกัน
+    ///
+    /// # Returns
+    /// * response จาก handler พร้อม path params ใน `request.param(..)`
+    /// * `HEAD` ที่ไม่มี route ของตัวเองจะใช้ handler ของ `GET` (server ตัด body ให้)
+    /// * `OPTIONS` ที่ไม่มี route ของตัวเองได้ 204 พร้อม `Allow` ที่สร้างจาก routes
+    /// * 405 พร้อม `Allow` header เมื่อ path ตรงแต่ method ไม่ตรง
+    /// * 404 เมื่อไม่มี route ไหนตรงกับ path
+    /// * 501 เมื่อไม่มี route ไหนรองรับ method นี้เลย (เช่น `CONNECT`, extension method)
+    pub fn handle(&self, request: &mut Request) -> Response {
+        match self.find(request) {
+            Ok((route, params)) => {
+                request.set_params(params);
+                route.call(request)
+            }
+            Err(response) => response,
+        }
+    }
+
+    /// 🔎 หา route ที่ตรงกับ method และ path พร้อม params ที่จับได้
+    /// ถ้าไม่เจอจะคืน response 404/405/501 (หรือ 204 ของ OPTIONS) ที่ควรส่งกลับแทน
+    fn find(&self, request: &Request) -> Result<(&Route, HashMap<String, String>), Response> {
+        let method = request.method();
+        if !self.supports(method) {
+            return Err(Response::new(HttpStatus::NotImplemented, None));
+        }
+
+        // 🌐 `OPTIONS *` ถามความสามารถของทั้ง server
+        if method == &Method::OPTIONS && request.path() == "*" {
+            let allow = allow_header(self.routes.iter().map(|route| &route.method));
+            return Err(Response::new(HttpStatus::NoContent, None).with_header("Allow", allow));
+        }
+
+        let mut matched: Vec<_> = self
+            .routes
+            .iter()
+            .filter_map(|route| Some((route, match_path(&route.pattern, request.path())?)))
+            .collect();
+        // 🏅 route ที่เจาะจงกว่าขึ้นก่อน (sort แบบ stable: เท่ากันยังเรียงตามลำดับที่ลงทะเบียน)
+        matched.sort_by_key(|(route, _)| specificity(&route.pattern));
+
+        // 🎯 method ตรงกันก่อน ถ้าเป็น HEAD และไม่มี route ของตัวเองใช้ route ของ GET แทน
+        let index = matched
+            .iter()
+            .position(|(route, _)| &route.method == method)
+            .or_else(|| match method {
+                Method::HEAD => matched
+                    .iter()
+                    .position(|(route, _)| route.method == Method::GET),
+                _ => None,
+            });
+        if let Some(index) = index {
+            return Ok(matched.swap_remove(index));
+        }
+
+        if matched.is_empty() {
+            return Err(Response::new(HttpStatus::NotFound, None));
+        }
+
+        let allow = allow_header(matched.iter().map(|(route, _)| &route.method));
+        let status = match method {
+            Method::OPTIONS => HttpStatus::NoContent,
+            _ => HttpStatus::MethodNotAllowed,
+        };
+        Err(Response::new(status, None).with_header("Allow", allow))
+    }
+
+    /// server รองรับ `method` หรือไม่: method มาตรฐานรองรับเสมอ (ได้ 404/405 ตามปกติ)
+    /// ส่วน `TRACE`, `CONNECT` และ extension method ต้องมี route ลงทะเบียนไว้
+    fn supports(&self, method: &Method) -> bool {
+        match method {
+            Method::TRACE | Method::CONNECT | Method::Extension(_) => {
+                self.routes.iter().any(|route| &route.method == method)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// 📋 ค่า `Allow` จาก methods ของ routes (เติม HEAD ให้ถ้ามี GET และ OPTIONS เสมอ)
+fn allow_header<'a>(methods: impl Iterator<Item = &'a Method>) -> String {
+    let mut allowed: Vec<&Method> = Vec::new();
+    for method in methods {
+        if !allowed.contains(&method) {
+            allowed.push(method);
+        }
+    }
+    if allowed.contains(&&Method::GET) && !allowed.contains(&&Method::HEAD) {
+        allowed.push(&Method::HEAD);
+    }
+    if !allowed.contains(&&Method::OPTIONS) {
+        allowed.push(&Method::OPTIONS);
+    }
+
+    allowed
+        .iter()
+        .map(|method| method.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Handler for Router {
+    /// 🧷 params ถูกเขียนลง request ตรงนี้ `handle_request` จึงไม่ต้องคัดลอก request
+    fn prepare_request(&mut self, request: &mut Request) {
+        if let Ok((_, params)) = self.find(request) {
+            request.set_params(params);
+        }
+    }
+
+    fn handle_request(&mut self, request: &Request) -> Response {
+        match self.find(request) {
+            Ok((route, _)) => route.call(request),
+            Err(response) => response,
+        }
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    split_path(pattern)
+        .map(|segment| {
+            if let Some(name) = segment.strip_prefix(':') {
+                Segment::Param(name.to_string())
+            } else if let Some(name) = segment.strip_prefix('*') {
+                Segment::Wildcard(name.to_string())
+            } else {
+                Segment::Static(segment.to_string())
+            }
+        })
+        .collect()
+}
+
+fn match_path(pattern: &[Segment], path: &str) -> Option<HashMap<String, String>> {
+    let mut params = HashMap::new();
+    let mut segments = split_path(path);
+
+    for (i, expected) in pattern.iter().enumerate() {
+        match expected {
+            Segment::Wildcard(name) => {
+                // 🌟 wildcard เก็บ path ที่เหลือทั้งหมด เช่น `css/site.css`
+                let rest = split_path(path)
+                    .skip(i)
+                    .map(decode_segment)
+                    .collect::<Option<Vec<_>>>()?
+                    .join("/");
+                params.insert(name.clone(), rest);
+                return Some(params);
+            }
+            Segment::Static(value) => {
+                if segments.next()? != value {
+                    return None;
+                }
+            }
+            Segment::Param(name) => {
+                params.insert(name.clone(), decode_segment(segments.next()?)?);
+            }
+        }
+    }
+
+    match segments.next() {
+        Some(_) => None,
+        None => Some(params),
+    }
+}
+
+/// 🏅 ลำดับความเจาะจงของ pattern ทีละ segment (น้อยกว่า = เจาะจงกว่า)
+fn specificity(pattern: &[Segment]) -> Vec<u8> {
+    pattern
+        .iter()
+        .map(|segment| match segment {
+            Segment::Static(_) => 0,
+            Segment::Param(_) => 1,
+            Segment::Wildcard(_) => 2,
+        })
+        .collect()
+}
+
+/// 🔡 percent-decode 1 segment (`J%C3%BCrgen` -> `Jürgen`) ไม่ใช่ UTF-8 ถือว่าไม่ตรง
+fn decode_segment(segment: &str) -> Option<String> {
+    String::from_utf8(percent_decode(segment)).ok()
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str) -> Request {
+        let raw = format!("{} {} HTTP/1.1\r\n\r\n", method, path);
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .get("/users/:id", |req| {
+                let id = req.param("id").unwrap_or_default();
+                Response::new(HttpStatus::Ok, Some(id.to_string()))
+            })
+            .delete("/users/:id", |_| Response::new(HttpStatus::Ok, None))
+            .get("/static/*rest", |req| {
+                let rest = req.param("rest").unwrap_or_default();
+                Response::new(HttpStatus::Ok, Some(rest.to_string()))
+            })
+    }
+
+    #[test]
+    fn extracts_named_and_wildcard_params() {
+        let router = router();
+
+        let mut user = request("GET", "/users/42");
+        router.handle(&mut user);
+        assert_eq!(user.param("id"), Some("42"));
+
+        let mut asset = request("GET", "/static/css/site.css");
+        router.handle(&mut asset);
+        assert_eq!(asset.param("rest"), Some("css/site.css"));
+    }
+
+    #[test]
+    fn handler_dispatch_sees_params_set_before_the_call() {
+        let mut router = router();
+        let mut user = request("GET", "/users/42");
+
+        let response = crate::http::Next::new(&[], &mut router).run(&mut user);
+        assert_eq!(user.param("id"), Some("42"));
+        let mut out = Vec::new();
+        response.send(&mut out).unwrap();
+        assert!(out.ends_with(b"\r\n\r\n42"));
+    }
+
+    #[test]
+    fn decodes_params_and_prefers_static_segments() {
+        let router = router()
+            .get("/users/me", |_| {
+                Response::new(HttpStatus::Ok, Some("me".to_string()))
+            })
+            .get("/static/css/:file", |_| Response::new(HttpStatus::Ok, None));
+
+        let mut encoded = request("GET", "/users/J%C3%BCrgen");
+        router.handle(&mut encoded);
+        let mut raw = request("GET", "/users/Jürgen");
+        router.handle(&mut raw);
+        assert_eq!(encoded.param("id"), Some("Jürgen"));
+        assert_eq!(raw.param("id"), Some("Jürgen"));
+
+        let mut asset = request("GET", "/static/img/a%20b.png");
+        router.handle(&mut asset);
+        assert_eq!(asset.param("rest"), Some("img/a b.png"));
+
+        // 🏅 ลงทะเบียนทีหลัง `/users/:id` แต่ยังชนะเพราะเจาะจงกว่า
+        let mut me = request("GET", "/users/me");
+        let response = router.handle(&mut me);
+        assert_eq!(me.param("id"), None);
+        let mut out = Vec::new();
+        response.send(&mut out).unwrap();
+        assert!(out.ends_with(b"\r\n\r\nme"));
+
+        let mut css = request("GET", "/static/css/site.css");
+        router.handle(&mut css);
+        assert_eq!(css.param("file"), Some("site.css"));
+        assert_eq!(css.param("rest"), None);
+    }
+
+    #[test]
+    fn method_mismatch_returns_405_with_allow() {
+        let response = router().handle(&mut request("POST", "/users/42"));
+
+        assert!(matches!(response.status(), HttpStatus::MethodNotAllowed));
+        assert_eq!(
+            response.headers().get("Allow"),
+            Some("GET, DELETE, HEAD, OPTIONS")
+        );
+    }
+
+    #[test]
+    fn head_falls_back_to_get_handler() {
+        let mut head = request("HEAD", "/users/7");
+        router().handle(&mut head);
+
+        assert_eq!(head.param("id"), Some("7"));
+    }
+
+    #[test]
+    fn options_lists_allowed_methods() {
+        let response = router().handle(&mut request("OPTIONS", "/users/7"));
+
+        assert!(matches!(response.status(), HttpStatus::NoContent));
+        assert_eq!(
+            response.headers().get("Allow"),
+            Some("GET, DELETE, HEAD, OPTIONS")
+        );
+    }
+
+    #[test]
+    fn unsupported_method_returns_501() {
+        let connect = router().handle(&mut request("CONNECT", "example.com:443"));
+        let extension = router().handle(&mut request("PROPFIND", "/users/7"));
+
+        assert!(matches!(connect.status(), HttpStatus::NotImplemented));
+        assert!(matches!(extension.status(), HttpStatus::NotImplemented));
+    }
+
+    #[test]
+    fn applies_route_cache_control() {
+        let router = router().cache_control(CacheControl::new().public().immutable());
+
+        let asset = router.handle(&mut request("GET", "/static/app.js"));
+        let user = router.handle(&mut request("GET", "/users/1"));
+
+        assert_eq!(
+            asset.headers().get("Cache-Control"),
+            Some("public, immutable")
+        );
+        assert!(!user.headers().contains("Cache-Control"));
+    }
+
+    #[test]
+    fn unknown_path_returns_404() {
+        let response = router().handle(&mut request("GET", "/users/42/posts"));
+
+        assert!(matches!(response.status(), HttpStatus::NotFound));
+    }
+}
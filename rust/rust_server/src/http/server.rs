@@ -1,85 +1,665 @@
-use chrono::Utc; // 🕰️ สำหรับจัดการ timestamp
+use chrono::Utc; // 🕰️ เวลาที่ได้รับ request สำหรับ access log
 
 // 🌐 นำเข้า HTTP types จาก module ของเรา
-use crate::http::{HttpStatus, Method, Request, Response, Result};
-use std::io::Read; // trait สำหรับอ่านข้อมูลจาก stream
-use std::net::TcpListener; // สำหรับ listen TCP connections
+use crate::http::{
+    AccessEntry, AccessLog, Compression, Error, HttpStatus, Limits, Method, Middleware, Next,
+    Request, RequestReader, Response, Result, ShutdownHandle, ThreadPool, cache, range,
+};
+#[cfg(feature = "tls")]
+use crate::http::{TlsConfig, tls};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream}; // สำหรับ listen TCP connections
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// 🧵 จำนวน worker เริ่มต้น: งานส่วนใหญ่รอ I/O จึงไม่ผูกกับจำนวน CPU
+const DEFAULT_WORKERS: usize = 4;
+
+/// 📬 จำนวน connection ที่รอ worker ได้ เกินแล้วตอบ 503 ทันที
+const DEFAULT_MAX_PENDING_CONNECTIONS: usize = 64;
+
+/// ⏱️ เวลาที่รอ request ที่ค้างอยู่ให้เสร็จหลังสั่ง shutdown
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 💤 เวลาที่ connection ว่างได้ก่อนถูกปิด (keep-alive idle timeout)
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 🔀 เวลาที่ connection หลัง upgrade (เช่น WebSocket) เงียบได้ก่อนถูกปิด
+const DEFAULT_UPGRADE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// ⏱️ เวลาที่รอเขียน 503 ให้ connection ที่ถูกปฏิเสธ (เขียนบน accept thread)
+const REJECT_WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// 🔁 จำนวน request สูงสุดต่อ 1 connection
+const DEFAULT_MAX_REQUESTS_PER_CONNECTION: usize = 100;
+
+/// 🎯 Handler trait: logic ของ application ที่ server เรียกใช้ในแต่ละ request
+///
+/// implement trait นี้เพื่อใช้ `Server` เป็น library กับ behavior ของตัวเอง
+/// (`Router` ก็ implement trait นี้)
+pub trait Handler {
+    /// 📨 สร้าง response สำหรับ request ที่ parse สำเร็จ
+    fn handle_request(&mut self, request: &Request) -> Response;
+
+    /// 🧷 เตรียม request ก่อนเรียก `handle_request` หลังผ่าน middleware ครบแล้ว
+    /// (ค่าเริ่มต้น: ไม่ทำอะไร `Router` ใช้เขียน path params ลง request โดยไม่ต้องคัดลอก)
+    fn prepare_request(&mut self, _request: &mut Request) {}
+
+    /// ⚠️ สร้าง response เมื่อ parse request ไม่สำเร็จ (ค่าเริ่มต้น: status จาก `HttpStatus::from(e)`)
+    fn handle_bad_request(&mut self, e: &Error) -> Response {
+        eprintln!("⚠️ Failed to parse request: {}", e);
+        Response::new(HttpStatus::from(e), None)
+    }
+}
+
+/// 🔌 stream ของ connection (TCP ธรรมดาหรือ TLS) ที่ยังเข้าถึง `TcpStream` ข้างใต้ได้
+/// (เช่นเพื่อเปลี่ยน read timeout หลัง upgrade เป็น WebSocket)
+pub(crate) trait Connection: Read + Write + Send + 'static {
+    fn socket(&self) -> &TcpStream;
+}
+
+impl Connection for TcpStream {
+    fn socket(&self) -> &TcpStream {
+        self
+    }
+}
+
+/// 🔀 connection หลังส่ง 101: อ่านต่อจาก buffer ของ `RequestReader` (ไม่ทิ้งข้อมูลที่อ่านเกินมา)
+struct Upgraded<S>(RequestReader<S>);
+
+impl<S: Connection> Read for Upgraded<S> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<S: Connection> Write for Upgraded<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.get_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.get_mut().flush()
+    }
+}
+
+impl<S: Connection> Connection for Upgraded<S> {
+    fn socket(&self) -> &TcpStream {
+        self.0.get_ref().socket()
+    }
+}
 
 /// 🌐 HTTP Server struct: จัดการ TCP connections และ HTTP requests
-/// รองรับ basic GET requests และ routing
-pub struct Server {
-    addr: String, // 📏 IP address และ port (เช่น "127.0.0.1:8000")
+/// logic ของแต่ละ request อยู่ใน `Handler` ที่ส่งเข้ามาตอนสร้าง server
+/// แต่ละ connection ถูกส่งไปทำใน `ThreadPool` ทำให้ client ที่ช้าไม่บล็อกคนอื่น
+pub struct Server<H: Handler> {
+    addr: String,                         // 📏 IP address และ port (เช่น "127.0.0.1:8000")
+    handler: H,                           // 🎯 application logic (เช่น `Router`)
+    limits: Limits,                       // 🚧 ขนาด header/body สูงสุดของแต่ละ request
+    workers: usize,                       // 🧵 จำนวน worker threads ที่จัดการ connections พร้อมกัน
+    max_pending: usize,                   // 📬 connection ที่รอ worker ได้ (เกินแล้วตอบ 503)
+    shutdown: ShutdownHandle,             // 🛑 สั่งหยุด server จาก thread อื่น
+    shutdown_timeout: Duration,           // ⏱️ deadline ของ request ที่ค้างอยู่ตอน shutdown
+    idle_timeout: Duration,               // 💤 keep-alive: ปิด connection ที่ว่างนานเกินนี้
+    upgrade_timeout: Duration,            // 🔀 read timeout ของ connection หลัง upgrade
+    max_requests: usize,                  // 🔁 keep-alive: จำนวน request สูงสุดต่อ connection
+    compression: Option<Compression>,     // 🗜️ บีบอัด response ตาม `Accept-Encoding` (ปิดไว้ถ้า `None`)
+    access_log: Option<AccessLog>,        // 📜 log ทุก request (ปิดไว้ถ้า `None`)
+    middleware: Vec<Arc<dyn Middleware>>, // 🧅 ครอบ handler ตามลำดับที่เพิ่ม (ตัวแรกอยู่นอกสุด)
+    #[cfg(feature = "tls")]
+    tls: Option<TlsConfig>, // 🔐 HTTPS (ปิดไว้ถ้า `None`)
+}
+
+/// ⚙️ ค่าที่ worker ต้องใช้ในการจัดการแต่ละ connection
+#[derive(Clone)]
+struct ConnectionConfig {
+    limits: Limits,
+    idle_timeout: Duration,
+    upgrade_timeout: Duration,
+    max_requests: usize,
+    shutdown: ShutdownHandle,
+    queued: Arc<AtomicUsize>, // 📬 connection ที่รอ worker อยู่ (มีคนรอก็เลิก keep-alive)
+    compression: Option<Compression>,
+    access_log: Option<AccessLog>,
+    middleware: Arc<[Arc<dyn Middleware>]>,
+    #[cfg(feature = "tls")]
+    tls: Option<Arc<rustls::ServerConfig>>,
 }
 
-impl Server {
+impl<H: Handler> Server<H> {
     /// 🏗️ Constructor: สร้าง Server instance ใหม่
     ///
     /// # Arguments  
     /// * `addr` - IP address และ port ในรูปแบบ string
+    /// * `handler` - application logic เช่น `Router`
     ///
     /// # Returns
     /// * Server instance
-    pub fn new(addr: String) -> Self {
-        Self { addr }
+    pub fn new(addr: String, handler: H) -> Self {
+        Self {
+            addr,
+            handler,
+            limits: Limits::default(),
+            workers: DEFAULT_WORKERS,
+            max_pending: DEFAULT_MAX_PENDING_CONNECTIONS,
+            shutdown: ShutdownHandle::new(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            upgrade_timeout: DEFAULT_UPGRADE_TIMEOUT,
+            max_requests: DEFAULT_MAX_REQUESTS_PER_CONNECTION,
+            compression: None,
+            access_log: Some(AccessLog::default()),
+            middleware: Vec::new(),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// 💤 กำหนดเวลาที่ keep-alive connection ว่างได้ก่อนถูกปิด (ค่าเริ่มต้น: 5 วินาที)
+    ///
+    /// ใช้กับทุก read: client ที่เงียบเกินเวลานี้กลาง request ได้ 408 ก่อนถูกปิด
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// 🔀 กำหนด read timeout ของ connection หลัง upgrade (ค่าเริ่มต้น: 60 วินาที)
+    ///
+    /// connection ที่ upgrade แล้ว (เช่น WebSocket) ใช้ worker 1 ตัวไปจนกว่าจะปิด
+    /// client ที่เงียบเกินเวลานี้ทำให้ `WebSocket::read` ได้ `Error::Timeout`
+    /// จึงไม่ค้าง worker ไว้ตลอด (handler เปลี่ยนเองได้ด้วย `WebSocket::set_read_timeout`)
+    pub fn with_upgrade_timeout(mut self, upgrade_timeout: Duration) -> Self {
+        self.upgrade_timeout = upgrade_timeout;
+        self
+    }
+
+    /// 🔁 กำหนดจำนวน request สูงสุดต่อ connection (ค่าเริ่มต้น: 100)
+    /// ครบแล้ว server จะตอบพร้อม `Connection: close`
+    pub fn with_max_requests_per_connection(mut self, max_requests: usize) -> Self {
+        self.max_requests = max_requests;
+        self
+    }
+
+    /// 🛑 handle สำหรับสั่ง shutdown (clone ไปไว้ใน signal handler หรือ test ได้)
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// ⏱️ กำหนดเวลาที่รอ request ที่ค้างอยู่หลังสั่ง shutdown (ค่าเริ่มต้น: 30 วินาที)
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// 🧵 กำหนดจำนวน worker threads (ค่าเริ่มต้น: 4)
+    ///
+    /// # Panics
+    /// * เมื่อ `run()` ถูกเรียกโดยที่ `workers` เป็น 0
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// 📬 กำหนดจำนวน connection ที่รอ worker ว่างได้ (ค่าเริ่มต้น: 64)
+    ///
+    /// เกินแล้ว connection ใหม่ได้ 503 พร้อม `Retry-After` ทันทีแทนที่จะกองรอไม่จำกัด
+    /// (ไม่นับ connection ที่ worker ว่างรับไปได้ทันที: 0 = ตอบ 503 เฉพาะตอนที่ทุก worker ไม่ว่าง)
+    /// และระหว่างที่มี connection รออยู่ worker จะเลิก keep-alive หลังตอบ request ปัจจุบัน
+    pub fn with_max_pending_connections(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending;
+        self
+    }
+
+    /// 🚧 กำหนด limits ทั้งหมดของ request (header, line, body)
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 🗜️ เปิดการบีบอัด response (gzip/deflate และ br เมื่อเปิด feature `brotli`)
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// 📜 กำหนด access log (ค่าเริ่มต้น: Common Log Format เวลา UTC ลง stdout)
+    pub fn with_access_log(mut self, access_log: AccessLog) -> Self {
+        self.access_log = Some(access_log);
+        self
+    }
+
+    /// 🔇 ปิด access log
+    pub fn without_access_log(mut self) -> Self {
+        self.access_log = None;
+        self
+    }
+
+    /// 🧅 เพิ่ม middleware ครอบ handler
+    ///
+    /// middleware ที่เพิ่มก่อนอยู่นอกสุด: เห็น request ก่อนและแก้ response ทีหลังสุด
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
     }
 
+    /// 🔐 รับเฉพาะ HTTPS ด้วย certificate ใน `tls` (ต้องเปิด feature `tls`)
+    #[cfg(feature = "tls")]
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// 📦 กำหนดขนาด request body สูงสุด เกินแล้วตอบ 413
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.limits.max_body_size = max_body_size;
+        self
+    }
+}
+
+impl<H: Handler + Clone + Send + 'static> Server<H> {
     /// 🚀 รัน HTTP server: main event loop
     ///
     /// # Process:
     /// 1. Bind TCP listener ไปยัง address
-    /// 2. รอ incoming connections
-    /// 3. อ่าน HTTP request จาก client  
-    /// 4. Parse request และ generate response
-    /// 5. ส่ง response กลับไป client
+    /// 2. รอ incoming connections จนกว่าจะถูกสั่ง shutdown
+    /// 3. ส่ง connection ให้ worker ใน `ThreadPool` (handler ถูก clone ไปต่อ connection)
+    /// 4. Worker อ่าน HTTP request จาก client (incremental จนครบ headers และ body)
+    /// 5. Worker generate response และส่งกลับไป client
     /// 6. วนซ้ำ
     ///
     /// # Returns
-    /// * `Result<()>` - Ok(()) เมื่อสำเร็จ, Err เมื่อเกิด error
-    pub fn run(&self) -> Result<()> {
-        println!("🌍 Server running on {}", self.addr);
-
+    /// * `Result<()>` - Ok(()) เมื่อ shutdown เรียบร้อย, Err เมื่อ bind ไม่สำเร็จ
+    pub fn run(self) -> Result<()> {
         // 🔌 Bind TCP listener ไปยัง address
         // ? operator: return early หาก bind ล้มเหลว
         let listener = TcpListener::bind(&self.addr)?;
+        self.serve(listener)
+    }
+
+    /// 🎧 รัน server บน listener ที่ bind ไว้แล้ว
+    /// (เช่น bind `127.0.0.1:0` ใน test เพื่อให้ OS เลือก port ว่างให้)
+    pub fn serve(self, listener: TcpListener) -> Result<()> {
+        let local_addr = listener.local_addr()?;
+        #[cfg(feature = "tls")]
+        let tls = self
+            .tls
+            .as_ref()
+            .map(TlsConfig::server_config)
+            .transpose()?;
+        self.shutdown.set_local_addr(local_addr);
+        // 🛑 สั่ง shutdown ก่อนมี `local_addr` จะไม่มีใครปลุก `accept()`: เช็คก่อนเข้า loop
+        // (เช็คหลัง `set_local_addr` เสมอ shutdown ที่มาทีหลังจึงเห็น address และปลุกได้)
+        if self.shutdown.is_shutdown() {
+            return Ok(());
+        }
+        println!(
+            "🌍 Server running on {} ({} workers)",
+            local_addr, self.workers
+        );
+
+        let pool = ThreadPool::with_queue_capacity(self.workers, self.max_pending);
+        #[cfg(feature = "tls")]
+        let tls_enabled = tls.is_some();
+        #[cfg(not(feature = "tls"))]
+        let tls_enabled = false;
+        let middleware: Arc<[Arc<dyn Middleware>]> = self.middleware.clone().into();
 
         // ♾️ Main server loop: รอและจัดการ incoming connections
         for stream in listener.incoming() {
-            let mut _stream = stream?; // 🌊 รับ TCP stream
-            let mut buffer: [u8; 1024] = [0; 1024]; // 🗂️ Buffer 1KB สำหรับอ่านข้อมูล
-            _stream.read(&mut buffer)?; // 📄 อ่าน request data
-
-            // 📝 Parse HTTP request จาก raw bytes
-            let request = Request::try_from(&buffer[..])?;
-
-            // 🕰️ สร้าง timestamp สำหรับ logging
-            let timestamp = std::time::SystemTime::now();
-            let date_time = chrono::DateTime::<Utc>::from(timestamp);
-            // 🇺🇭 แปลงเป็นเวลาไทย (+7 hours)
-            let thai_date_time =
-                date_time.with_timezone(&chrono::FixedOffset::east_opt(7 * 3600).unwrap());
-
-            // 📊 Log request information
-            println!(
-                "[{}] {:?} {}",
-                thai_date_time,
-                request.method(), // HTTP method (GET, POST, etc.)
-                request.path()    // URL path (/hello, /, etc.)
-            );
+            // 🛑 ถูกสั่ง shutdown: หยุดรับ connection ใหม่ (connection ที่ปลุกเราถูกทิ้งไป)
+            if self.shutdown.is_shutdown() {
+                break;
+            }
 
-            // 🎣 Route handling: จับคู่ HTTP method และ path
-            let response = match request.method() {
-                Method::GET => match request.path().as_str() {
-                    "/" => Response::new(HttpStatus::Ok, Some("home".to_string())), // 🏠 Home page
-                    "/hello" => Response::new(HttpStatus::Ok, Some("hello".to_string())), // 👋 Hello page
-                    _ => Response::new(crate::http::HttpStatus::NotFound, None), // ❌ 404 Not Found
-                },
-                _ => Response::new(crate::http::HttpStatus::NotFound, None), // ❌ Method not allowed
+            // 🌊 รับ TCP stream: accept ล้มเหลว (เช่น client reset) ไม่ควรหยุดทั้ง server
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("⚠️ Failed to accept connection: {}", e);
+                    continue;
+                }
             };
 
-            // 📤 ส่ง HTTP response กลับไป client
-            response.send(&mut _stream)?;
+            // 🚧 worker ไม่ว่างและมีคนรอเต็ม queue แล้ว: ปฏิเสธแทนการกองรอไปเรื่อยๆ
+            if pool.is_full() {
+                self.reject_busy(stream, tls_enabled);
+                continue;
+            }
+
+            // 🧵 ส่ง connection ให้ worker: main thread กลับไป accept ต่อได้ทันที
+            let mut handler = self.handler.clone();
+            let config = ConnectionConfig {
+                limits: self.limits,
+                idle_timeout: self.idle_timeout,
+                upgrade_timeout: self.upgrade_timeout,
+                max_requests: self.max_requests,
+                shutdown: self.shutdown.clone(),
+                queued: pool.queued_counter(),
+                compression: self.compression.clone(),
+                access_log: self.access_log.clone(),
+                middleware: Arc::clone(&middleware),
+                #[cfg(feature = "tls")]
+                tls: tls.clone(),
+            };
+            pool.execute(move || {
+                // 🛡️ error ของ connection หนึ่งจบแค่ที่ connection นั้น
+                if let Err(e) = serve_connection(&mut handler, stream, &config) {
+                    eprintln!("⚠️ Connection dropped: {}", e);
+                }
+            });
         } // 🔁 วนกลับไปรอ connection ถัดไป
 
+        // ⏳ ให้ request ที่ค้างอยู่ทำงานให้เสร็จภายใน deadline
+        println!("🛑 Shutting down, waiting for in-flight requests...");
+        if !pool.shutdown_timeout(self.shutdown_timeout) {
+            eprintln!(
+                "⚠️ Shutdown timeout ({:?}) reached, abandoning in-flight requests",
+                self.shutdown_timeout
+            );
+        }
+
         Ok(())
     }
+
+    /// 🚫 ตอบ 503 ให้ connection ที่ไม่มีที่รอ worker (บน accept thread จึงต้องไม่ค้าง)
+    ///
+    /// connection ที่เป็น TLS ปิดทิ้งเลย: ต้อง handshake ก่อนถึงจะตอบ HTTP ได้
+    fn reject_busy(&self, mut stream: TcpStream, tls_enabled: bool) {
+        if tls_enabled {
+            return;
+        }
+
+        let (time, started) = (Utc::now(), Instant::now());
+        let response = Response::new(HttpStatus::ServiceUnavailable, None)
+            .with_header("Retry-After", "1")
+            .with_header("Connection", "close");
+        let status = *response.status();
+        // ⏱️ client ที่ไม่อ่าน response ต้องไม่ทำให้ accept loop ค้าง
+        let _ = stream.set_write_timeout(Some(REJECT_WRITE_TIMEOUT));
+        let Ok(bytes_sent) = response.send(&mut stream) else {
+            return;
+        };
+
+        if let Some(access_log) = &self.access_log {
+            access_log.log(&AccessEntry {
+                peer_addr: stream.peer_addr().ok(),
+                time,
+                request: None,
+                status,
+                bytes_sent,
+                duration: started.elapsed(),
+            });
+        }
+    }
+}
+
+/// 🔌 เตรียม connection ที่เพิ่ง accept (timeout, TLS handshake) แล้วส่งต่อให้ `handle_connection`
+fn serve_connection<H: Handler>(
+    handler: &mut H,
+    stream: TcpStream,
+    config: &ConnectionConfig,
+) -> Result<()> {
+    // 💤 read ที่รอนานเกิน idle timeout จะได้ `Error::Timeout` (รวมถึงตอน handshake)
+    stream.set_read_timeout(Some(config.idle_timeout))?;
+    let peer_addr = stream.peer_addr().ok();
+
+    #[cfg(feature = "tls")]
+    if let Some(tls) = &config.tls {
+        let stream = tls::accept(tls, stream)?;
+        return handle_connection(handler, stream, peer_addr, config);
+    }
+
+    handle_connection(handler, stream, peer_addr, config)
+}
+
+/// 🔗 จัดการ connection เดียว: อ่าน request, เรียก handler, ส่ง response
+/// วนซ้ำบน connection เดิม (keep-alive) จนกว่า client ขอปิด, ว่างเกิน idle timeout,
+/// ครบ max requests หรือ server ถูกสั่ง shutdown
+///
+/// request ที่ pipeline มาติดกันจะค้างอยู่ใน buffer ของ `RequestReader`
+/// และถูกตอบตามลำดับในรอบถัดไป
+///
+/// # Returns
+/// * `Err` เฉพาะ I/O error (connection ใช้ต่อไม่ได้แล้ว)
+///   ส่วน request ที่ parse ไม่ได้จะถูกตอบด้วย 4xx/5xx ผ่าน `handle_bad_request`
+fn handle_connection<H: Handler, S: Connection>(
+    handler: &mut H,
+    stream: S,
+    peer_addr: Option<SocketAddr>,
+    config: &ConnectionConfig,
+) -> Result<()> {
+    let mut reader = RequestReader::new(stream, config.limits);
+
+    for served in 1.. {
+        // 📖 อ่าน request แบบ incremental จนครบ head และ body
+        let mut request = match reader.read_request() {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()), // 🔌 client ปิด connection
+            // 💤 ว่างรอ request ถัดไปนานเกิน idle timeout
+            Err(Error::Timeout) if !reader.is_mid_request() => return Ok(()),
+            Err(e @ Error::IO(_)) => return Err(e),
+            Err(e) => {
+                // ⚠️ request ไม่ถูกต้องหรือหยุดส่งกลางทาง: ตอบ 400/408/505/... แล้วปิด connection
+                // (อ่านต่อไม่ได้เพราะไม่รู้ว่า request ถัดไปเริ่มตรงไหน)
+                let (time, started) = (Utc::now(), Instant::now());
+                let response = handler
+                    .handle_bad_request(&e)
+                    .with_header("Connection", "close");
+                let status = *response.status();
+                let bytes_sent = response.send(reader.get_mut())?;
+                if let Some(access_log) = &config.access_log {
+                    access_log.log(&AccessEntry {
+                        peer_addr,
+                        time,
+                        request: None,
+                        status,
+                        bytes_sent,
+                        duration: started.elapsed(),
+                    });
+                }
+                return Ok(());
+            }
+        };
+        let (time, started) = (Utc::now(), Instant::now());
+
+        // 🎣 ส่ง request ผ่าน middleware ให้ handler สร้าง response
+        let response = Next::new(&config.middleware, handler).run(&mut request);
+        let mut response = finish_response(&request, response, config.compression.as_ref());
+
+        // 🔀 101 Switching Protocols: ส่ง response แล้วยก connection ให้ handler ของ protocol ใหม่
+        if let Some(upgrade) = response.take_upgrade() {
+            let status = *response.status();
+            let bytes_sent = response.send(reader.get_mut())?;
+            if let Some(access_log) = &config.access_log {
+                access_log.log(&AccessEntry {
+                    peer_addr,
+                    time,
+                    request: Some(&request),
+                    status,
+                    bytes_sent,
+                    duration: started.elapsed(),
+                });
+            }
+            // 💤 protocol ใหม่ (เช่น WebSocket) เงียบได้นานกว่า idle timeout ของ HTTP
+            // แต่ยังต้องมี timeout: ไม่งั้น client ที่หายไปเฉยๆ จะค้าง worker ไว้ตลอด
+            reader
+                .get_ref()
+                .socket()
+                .set_read_timeout(Some(config.upgrade_timeout))?;
+            upgrade.run(Box::new(Upgraded(reader)));
+            return Ok(());
+        }
+
+        // 🔁 ใช้ connection ต่อได้ถ้าทั้งสองฝั่งไม่ขอปิด และยังไม่ถึง limit
+        let keep_alive = request.keep_alive()
+            && !response.closes_connection()
+            && served < config.max_requests
+            && !config.shutdown.is_shutdown()
+            // 📬 มี connection รอ worker อยู่: คืน worker ให้คนที่รอแทนการถือไว้รอ request ถัดไป
+            && config.queued.load(Ordering::SeqCst) == 0;
+        if !keep_alive {
+            response = response.with_header("Connection", "close");
+        }
+
+        // 📤 ส่ง HTTP response กลับไป client
+        let status = *response.status();
+        let bytes_sent = response.send(reader.get_mut())?;
+
+        if let Some(access_log) = &config.access_log {
+            access_log.log(&AccessEntry {
+                peer_addr,
+                time,
+                request: Some(&request),
+                status,
+                bytes_sent,
+                duration: started.elapsed(),
+            });
+        }
+
+        if !keep_alive {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// 🧰 ขั้นตอนหลัง handler ที่ server ทุกแบบทำเหมือนกัน: บีบอัด, 304, Range และ HEAD
+pub(crate) fn finish_response(
+    request: &Request,
+    mut response: Response,
+    compression: Option<&Compression>,
+) -> Response {
+    // 🗜️ บีบอัดก่อนเช็ค 304 เพื่อให้เทียบกับ ETag ของ representation ที่บีบอัดแล้ว
+    if let Some(compression) = compression {
+        response = compression.apply(request, response);
+    }
+    // ✅ client มีของล่าสุดแล้ว (If-None-Match / If-Modified-Since) ตอบ 304 แทน
+    response = cache::conditional(request, response);
+    // ✂️ `Range: bytes=...` ตอบเฉพาะส่วนที่ขอด้วย 206
+    response = range::apply(request, response);
+    if request.method() == &Method::HEAD {
+        response = response.head_only(); // 🙈 HEAD: headers เหมือน GET แต่ไม่มี body
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::Router;
+    use std::io::{Read, Write};
+    use std::thread;
+
+    #[test]
+    fn serves_pipelined_requests_and_stops_on_shutdown() {
+        let router = Router::new().get("/", |_| {
+            Response::new(HttpStatus::Ok, Some("home".to_string()))
+        });
+        let server = Server::new("127.0.0.1:0".to_string(), router);
+        let shutdown = server.shutdown_handle();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\n\r\nGET / HTTP/1.1\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        // 🔁 pipelined requests ถูกตอบตามลำดับบน connection เดียว แล้วปิดตาม `Connection: close`
+        assert_eq!(response.matches("HTTP/1.1 200 OK").count(), 2);
+        assert!(response.contains("\r\nConnection: close\r\n"));
+        assert!(response.ends_with("\r\n\r\nhome"));
+
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn answers_408_to_requests_that_stall_midway() {
+        let router = Router::new().post("/", |_| Response::new(HttpStatus::Ok, None));
+        let server = Server::new("127.0.0.1:0".to_string(), router)
+            .with_idle_timeout(Duration::from_millis(100))
+            .without_access_log();
+        let shutdown = server.shutdown_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        // 💤 ไม่ส่งอะไรเลย: ปิดเงียบๆ
+        let mut idle = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+        idle.read_to_string(&mut response).unwrap();
+        assert!(response.is_empty());
+
+        // 🧊 หยุดกลาง head และกลาง body: ได้ 408
+        for partial in [
+            &b"POST / HTTP/1.1\r\nHost"[..],
+            b"POST / HTTP/1.1\r\nContent-Length: 9\r\n\r\nab",
+        ] {
+            let mut stalled = TcpStream::connect(addr).unwrap();
+            stalled.write_all(partial).unwrap();
+            let mut response = String::new();
+            stalled.read_to_string(&mut response).unwrap();
+            assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+        }
+
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn shutdown_before_serve_returns_without_a_connection() {
+        let server = Server::new("127.0.0.1:0".to_string(), Router::new()).without_access_log();
+        server.shutdown_handle().shutdown();
+
+        // ⏱️ ยังไม่มี address ให้ปลุกตอนสั่ง: ถ้าไม่เช็คก่อนเข้า loop จะค้างที่ `accept()`
+        let (done, finished) = std::sync::mpsc::channel();
+        thread::spawn(move || {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            done.send(server.serve(listener).is_ok()).unwrap();
+        });
+        assert_eq!(finished.recv_timeout(Duration::from_secs(5)), Ok(true));
+    }
+
+    #[test]
+    fn rejects_connections_beyond_the_pending_limit_with_503() {
+        let router = Router::new().get("/", |_| {
+            Response::new(HttpStatus::Ok, Some("home".to_string()))
+        });
+        let server = Server::new("127.0.0.1:0".to_string(), router)
+            .with_workers(1)
+            .with_max_pending_connections(1)
+            .without_access_log();
+        let shutdown = server.shutdown_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        // 🧵 connection แรกถือ worker ตัวเดียวไว้ (keep-alive รอ request ถัดไป)
+        let mut holding = TcpStream::connect(addr).unwrap();
+        holding.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = Vec::new();
+        while !response.ends_with(b"home") {
+            let mut buf = [0; 1024];
+            let read = holding.read(&mut buf).unwrap();
+            response.extend_from_slice(&buf[..read]);
+        }
+
+        // 📬 ตัวที่สองรอใน queue ตัวที่สามไม่มีที่รอแล้ว: ได้ 503 ทันที
+        let waiting = TcpStream::connect(addr).unwrap();
+        let mut rejected = TcpStream::connect(addr).unwrap();
+        let mut response = String::new();
+        rejected.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+        assert!(response.contains("\r\nRetry-After: 1\r\n"));
+
+        drop((holding, waiting));
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
 }
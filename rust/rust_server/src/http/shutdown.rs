@@ -0,0 +1,61 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 🛑 ShutdownHandle: สั่งให้ `Server` หยุดรับ connection ใหม่จาก thread อื่น
+///
+/// clone ได้และส่งข้าม thread ได้ (เช่น ไปไว้ใน signal handler หรือใน test)
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle {
+    inner: Arc<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    requested: AtomicBool,
+    local_addr: Mutex<Option<SocketAddr>>, // 📍 address ที่ server bind อยู่ (ใช้ปลุก accept)
+}
+
+impl ShutdownHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 🛑 ขอให้ server หยุด: accept loop จะจบ แล้วรอ request ที่ค้างอยู่ตาม deadline
+    pub fn shutdown(&self) {
+        self.inner.requested.store(true, Ordering::SeqCst);
+
+        // 🔔 `accept()` block อยู่ จึงต่อ connection เปล่าเข้าไปปลุกให้เห็น flag
+        if let Some(mut addr) = self.local_addr() {
+            // bind ไว้ที่ 0.0.0.0 / [::] ให้ต่อผ่าน loopback แทน
+            if addr.ip().is_unspecified() {
+                addr.set_ip(match addr {
+                    SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                });
+            }
+            let _ = TcpStream::connect(addr);
+        }
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.requested.load(Ordering::SeqCst)
+    }
+
+    /// 📍 address ที่ server bind อยู่ (มีค่าหลังจาก server เริ่ม listen แล้ว)
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *self
+            .inner
+            .local_addr
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn set_local_addr(&self, addr: SocketAddr) {
+        *self
+            .inner
+            .local_addr
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(addr);
+    }
+}
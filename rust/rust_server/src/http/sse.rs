@@ -0,0 +1,293 @@
+use crate::http::{Error, HttpStatus, Request, Response, Result};
+use std::fmt::{Display, Write as _};
+use std::io::Read;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
+use std::thread;
+use std::time::Duration;
+
+/// 💓 ส่ง comment เปล่าทุกช่วงนี้ถ้าไม่มี event (กัน proxy ตัด connection ที่เงียบนาน)
+const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// 📬 จำนวน event ที่รอส่งได้ก่อน `EventSender::send` ต้องรอ (backpressure)
+const CHANNEL_CAPACITY: usize = 16;
+
+/// 📨 Event: 1 event ของ `text/event-stream`
+///
+/// ```
+/// use rust_server::http::sse::Event;
+/// use std::time::Duration;
+///
+/// let event = Event::new("line 1\nline 2")
+///     .event("update")
+///     .id("42")
+///     .retry(Duration::from_secs(3));
+/// assert_eq!(
+///     event.to_string(),
+///     "event: update\nid: 42\nretry: 3000\ndata: line 1\ndata: line 2\n\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Event {
+    event: Option<String>,
+    id: Option<String>,
+    retry: Option<Duration>,
+    data: String,
+}
+
+impl Event {
+    /// 📦 event ที่มี `data` (หลายบรรทัดได้ จะถูกแยกเป็นหลาย `data:`)
+    pub fn new(data: impl Into<String>) -> Self {
+        Self {
+            data: data.into(),
+            ..Self::default()
+        }
+    }
+
+    /// 🏷️ ชื่อ event (client ฟังด้วย `addEventListener(name, ...)`)
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(single_line(event.into()));
+        self
+    }
+
+    /// 🔖 id ที่ client จะส่งกลับมาใน `Last-Event-ID` ตอนต่อใหม่
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        // NUL ใน id ทำให้ browser ไม่สนใจ id นั้น จึงตัดทิ้งด้วย
+        self.id = Some(single_line(id.into()).replace('\0', ""));
+        self
+    }
+
+    /// ⏱️ ให้ client รอเท่านี้ก่อนต่อใหม่เมื่อหลุด
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(event) = &self.event {
+            writeln!(f, "event: {}", event)?;
+        }
+        if let Some(id) = &self.id {
+            writeln!(f, "id: {}", id)?;
+        }
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {}", retry.as_millis())?;
+        }
+        // 📝 แยกบรรทัดตามที่ spec นับ (`\r\n`, `\r` และ `\n`)
+        for line in self.data.replace("\r\n", "\n").split(['\r', '\n']) {
+            writeln!(f, "data: {}", line)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// ✂️ `event`/`id` ต้องอยู่ในบรรทัดเดียว ไม่งั้นจะกลายเป็น field อื่น
+fn single_line(value: String) -> String {
+    if value.contains(['\r', '\n']) {
+        value.replace(['\r', '\n'], "")
+    } else {
+        value
+    }
+}
+
+/// 🔖 `Last-Event-ID` ที่ client ส่งมาตอนต่อใหม่
+pub fn last_event_id(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get("Last-Event-ID")
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+}
+
+/// 📤 EventSender: ส่ง event เข้า stream จาก thread ของ producer
+pub struct EventSender {
+    sender: SyncSender<String>,
+    last_event_id: Option<String>,
+}
+
+impl EventSender {
+    /// 📨 ส่ง event (รอถ้ามี event ค้างส่งอยู่เยอะ)
+    ///
+    /// # Returns
+    /// * `Err` เมื่อ client หลุดไปแล้ว: producer ควรหยุดทำงาน
+    pub fn send(&self, event: &Event) -> Result<()> {
+        self.sender
+            .send(event.to_string())
+            .map_err(|_| Error::IO("event stream closed by client".to_string()))
+    }
+
+    /// 💬 ส่ง comment (client ไม่เห็นเป็น event เหมาะกับข้อความ debug)
+    pub fn comment(&self, comment: &str) -> Result<()> {
+        let mut lines = String::new();
+        for line in comment.split(['\r', '\n']) {
+            let _ = writeln!(lines, ": {}", line);
+        }
+        lines.push('\n');
+        self.sender
+            .send(lines)
+            .map_err(|_| Error::IO("event stream closed by client".to_string()))
+    }
+
+    /// 🔖 `Last-Event-ID` ของ request: ส่งต่อจาก event ถัดจากนี้
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+}
+
+/// 📡 EventStream: response แบบ `text/event-stream` ที่ส่ง event ไปเรื่อยๆ
+///
+/// `producer` ทำงานใน thread ของตัวเอง ส่ง event ผ่าน `EventSender`
+/// แต่ละ event ถูกส่งเป็น chunk และ flush ทันที ถ้าไม่มี event นานเกิน keep-alive
+/// จะส่ง comment `: keep-alive` ไปแทน stream จบเมื่อ producer return
+///
+/// connection ของ SSE ใช้ worker thread ไปตลอดที่ stream ยังเปิดอยู่
+/// (ตั้ง `Server::with_workers` ให้พอกับจำนวน client ที่ฟังพร้อมกัน)
+///
+/// ```
+/// use rust_server::http::Router;
+/// use rust_server::http::sse::{self, Event};
+///
+/// let router = Router::new().get("/ticks", |req| {
+///     sse::stream(req, |events| {
+///         // 🔁 ต่อจาก id ที่ client เห็นล่าสุด
+///         let start: u64 = events.last_event_id().and_then(|id| id.parse().ok()).unwrap_or(0);
+///         for tick in start + 1.. {
+///             let event = Event::new(format!("tick {}", tick)).id(tick.to_string());
+///             if events.send(&event).is_err() {
+///                 break; // 🔌 client หลุดแล้ว
+///             }
+///             std::thread::sleep(std::time::Duration::from_secs(1));
+///         }
+///     })
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventStream {
+    keep_alive: Duration,
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        Self {
+            keep_alive: DEFAULT_KEEP_ALIVE,
+        }
+    }
+}
+
+impl EventStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 💓 ช่วงเวลาส่ง keep-alive comment เมื่อไม่มี event (ค่าเริ่มต้น: 15 วินาที)
+    pub fn with_keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// 📡 เริ่ม `producer` แล้วคืน response ที่ stream event ของมัน
+    pub fn respond(
+        &self,
+        request: &Request,
+        producer: impl FnOnce(EventSender) + Send + 'static,
+    ) -> Response {
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let sender = EventSender {
+            sender,
+            last_event_id: last_event_id(request).map(str::to_string),
+        };
+        thread::spawn(move || producer(sender));
+
+        Response::builder(HttpStatus::Ok)
+            .header("Content-Type", "text/event-stream")
+            .header("Cache-Control", "no-cache")
+            // 🧯 nginx: อย่า buffer response นี้
+            .header("X-Accel-Buffering", "no")
+            .stream(
+                EventReader {
+                    receiver,
+                    keep_alive: self.keep_alive,
+                    pending: Vec::new(),
+                },
+                None,
+            )
+            .build()
+    }
+}
+
+/// 📡 `EventStream` ด้วยค่าเริ่มต้น
+pub fn stream(request: &Request, producer: impl FnOnce(EventSender) + Send + 'static) -> Response {
+    EventStream::default().respond(request, producer)
+}
+
+/// 📖 body ของ SSE: อ่านทีละ event จาก channel (หรือ keep-alive เมื่อรอนานเกิน)
+struct EventReader {
+    receiver: Receiver<String>,
+    keep_alive: Duration,
+    pending: Vec<u8>, // 📦 ส่วนของ event ที่ยังไม่ได้ส่ง (event ใหญ่กว่า buffer ของ `read`)
+}
+
+impl Read for EventReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            self.pending = match self.receiver.recv_timeout(self.keep_alive) {
+                Ok(event) => event.into_bytes(),
+                Err(RecvTimeoutError::Timeout) => b": keep-alive\n\n".to_vec(),
+                Err(RecvTimeoutError::Disconnected) => return Ok(0), // 🏁 producer จบแล้ว
+            };
+        }
+
+        let read = self.pending.len().min(buf.len());
+        buf[..read].copy_from_slice(&self.pending[..read]);
+        self.pending.drain(..read);
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &str) -> Request {
+        let raw = format!("GET /events HTTP/1.1\r\n{}\r\n", headers);
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn formats_fields_and_strips_injected_newlines() {
+        let event = Event::new("a\r\nb\rc").event("up\ndate").id("7\n");
+        assert_eq!(
+            event.to_string(),
+            "event: update\nid: 7\ndata: a\ndata: b\ndata: c\n\n"
+        );
+        assert_eq!(Event::new("").to_string(), "data: \n\n");
+    }
+
+    #[test]
+    fn streams_events_with_keep_alive_and_last_event_id() {
+        let response = EventStream::new()
+            .with_keep_alive(Duration::from_millis(20))
+            .respond(&request("Last-Event-ID: 41\r\n"), |events| {
+                let next = events.last_event_id().unwrap().parse::<u64>().unwrap() + 1;
+                events
+                    .send(&Event::new("first").id(next.to_string()))
+                    .unwrap();
+                thread::sleep(Duration::from_millis(70));
+                events.send(&Event::new("second")).unwrap();
+            });
+        assert_eq!(
+            response.headers().get("Content-Type"),
+            Some("text/event-stream")
+        );
+
+        let mut sent = Vec::new();
+        response.send(&mut sent).unwrap();
+        let sent = String::from_utf8(sent).unwrap();
+
+        assert!(sent.contains("Transfer-Encoding: chunked\r\n"));
+        assert!(sent.contains("\r\nid: 42\ndata: first\n\n\r\n"));
+        assert!(sent.contains(": keep-alive\n\n"));
+        assert!(sent.ends_with("data: second\n\n\r\n0\r\n\r\n"));
+    }
+}
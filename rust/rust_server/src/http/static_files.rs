@@ -0,0 +1,291 @@
+use crate::http::query_string::percent_decode;
+use crate::http::{CacheControl, Handler, HttpStatus, Method, Request, Response, cache};
+use std::fs::File;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+/// 📁 directory เริ่มต้นของไฟล์ static
+const DEFAULT_ROOT: &str = "public";
+
+/// 📄 ไฟล์ที่ส่งแทนเมื่อ request เป็น directory
+const INDEX_FILE: &str = "index.html";
+
+/// 📁 StaticFiles: ส่งไฟล์จาก directory `root` ตาม path ของ request
+///
+/// ใช้เป็น `Handler` ตรงๆ หรือผูกกับ wildcard route ของ `Router`:
+///
+/// ```
+/// use rust_server::http::{Router, StaticFiles};
+///
+/// let files = StaticFiles::new("public");
+/// let router = Router::new().get("/assets/*path", move |req| {
+///     files.serve(req.param("path").unwrap_or_default())
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct StaticFiles {
+    root: PathBuf,
+    cache_control: Option<CacheControl>, // 🗄️ `Cache-Control` ของทุกไฟล์ (ถ้ากำหนด)
+}
+
+impl Default for StaticFiles {
+    fn default() -> Self {
+        Self::new(DEFAULT_ROOT)
+    }
+}
+
+impl StaticFiles {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            cache_control: None,
+        }
+    }
+
+    /// 🗄️ กำหนด `Cache-Control` ของไฟล์ที่ส่ง (เช่น `max-age` ยาวๆ สำหรับ assets)
+    pub fn with_cache_control(mut self, policy: CacheControl) -> Self {
+        self.cache_control = Some(policy);
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 📤 ส่งไฟล์ตาม `path` (relative กับ `root` และ percent-decode แล้ว เช่นค่าจาก `Router` param)
+    ///
+    /// # Returns
+    /// * 200 พร้อม `Content-Type` ตามนามสกุลไฟล์ (directory ส่ง `index.html`),
+    ///   `ETag` และ `Last-Modified`
+    /// * 403 เมื่อ path พยายามออกนอก `root` (`..`, absolute path)
+    /// * 404 เมื่อไม่มีไฟล์
+    pub fn serve(&self, path: &str) -> Response {
+        let Some(relative) = sanitize(path) else {
+            return Response::new(HttpStatus::Forbidden, None);
+        };
+
+        let mut file_path = self.root.join(relative);
+        if file_path.is_dir() {
+            file_path.push(INDEX_FILE);
+        }
+
+        // 🔗 symlink ที่ชี้ออกนอก root ก็ถือเป็น traversal
+        let inside_root = match (file_path.canonicalize(), self.root.canonicalize()) {
+            (Ok(file), Ok(root)) => file.starts_with(root),
+            (Err(e), _) | (_, Err(e)) => return error_response(e.kind()),
+        };
+        if !inside_root {
+            return Response::new(HttpStatus::Forbidden, None);
+        }
+
+        let file = match File::open(&file_path) {
+            Ok(file) => file,
+            Err(e) => return error_response(e.kind()),
+        };
+        let metadata = match file.metadata() {
+            Ok(metadata) if metadata.is_file() => metadata,
+            Ok(_) => return Response::new(HttpStatus::NotFound, None),
+            Err(e) => return error_response(e.kind()),
+        };
+        let length = metadata.len();
+
+        let mut response =
+            Response::builder(HttpStatus::Ok).header("Content-Type", content_type(&file_path));
+        // 🏷️ ETag/Last-Modified ให้ browser ถามซ้ำด้วย If-None-Match/If-Modified-Since แล้วได้ 304
+        if let Ok(modified) = metadata.modified() {
+            response = response
+                .etag(cache::etag_for_file(modified, length))
+                .last_modified(modified);
+        }
+        if let Some(policy) = &self.cache_control {
+            response = response.cache_control(policy);
+        }
+        response.file(file, length).build()
+    }
+}
+
+impl Handler for StaticFiles {
+    fn handle_request(&mut self, request: &Request) -> Response {
+        match request.method() {
+            Method::GET | Method::HEAD => {
+                // 🔡 path ดิบของ request ยังไม่ decode (`%2e%2e` ถูกตรวจหลัง decode ใน `sanitize`)
+                let path = request.path().strip_prefix('/').unwrap_or(request.path());
+                match String::from_utf8(percent_decode(path)) {
+                    Ok(path) => self.serve(&path),
+                    Err(_) => Response::new(HttpStatus::Forbidden, None),
+                }
+            }
+            _ => {
+                Response::new(HttpStatus::MethodNotAllowed, None).with_header("Allow", "GET, HEAD")
+            }
+        }
+    }
+}
+
+/// 🛡️ ตรวจว่า path (decode แล้ว) อยู่ใต้ root จริง: คืน `None` ถ้ามี `..`,
+/// เป็น absolute path, มี backslash หรือ NUL
+fn sanitize(path: &str) -> Option<PathBuf> {
+    if path.contains(['\\', '\0']) {
+        return None;
+    }
+
+    let mut relative = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(segment) => relative.push(segment),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(relative)
+}
+
+fn error_response(kind: ErrorKind) -> Response {
+    let status = match kind {
+        ErrorKind::NotFound | ErrorKind::NotADirectory => HttpStatus::NotFound,
+        ErrorKind::PermissionDenied => HttpStatus::Forbidden,
+        _ => HttpStatus::InternalServerError,
+    };
+    Response::new(status, None)
+}
+
+/// 🏷️ เดา `Content-Type` จากนามสกุลไฟล์ (ไม่รู้จักส่งเป็น binary)
+fn content_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "txt" => "text/plain; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "xml" => "application/xml",
+        "md" => "text/markdown; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// 🧹 ลบ directory ชั่วคราวทิ้งเมื่อ test จบ (แม้ assert จะ fail)
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// 🗂️ สร้าง directory ชั่วคราวที่มี `index.html`, `css/site.css` และไฟล์ลับนอก root
+    fn fixture(name: &str) -> (TempDir, StaticFiles) {
+        let base = TempDir(std::env::temp_dir().join(format!(
+            "rust_server_{}_{}",
+            name,
+            std::process::id()
+        )));
+        let root = base.0.join("public");
+        fs::create_dir_all(root.join("css")).unwrap();
+        fs::write(root.join("index.html"), "<h1>home</h1>").unwrap();
+        fs::write(root.join("css/site.css"), "body {}").unwrap();
+        fs::write(base.0.join("secret.txt"), "secret").unwrap();
+        (base, StaticFiles::new(root))
+    }
+
+    fn body_of(response: Response) -> String {
+        let mut out = Vec::new();
+        response.send(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        out.split_once("\r\n\r\n").unwrap().1.to_string()
+    }
+
+    #[test]
+    fn serves_files_with_content_type_and_index() {
+        let (_dir, files) = fixture("serve");
+
+        let css = files.serve("css/site.css");
+        assert!(matches!(css.status(), HttpStatus::Ok));
+        assert_eq!(
+            css.headers().get("Content-Type"),
+            Some("text/css; charset=utf-8")
+        );
+        assert!(css.headers().contains("ETag"));
+        assert!(css.headers().contains("Last-Modified"));
+        assert_eq!(body_of(css), "body {}");
+
+        let index = files.serve("");
+        assert_eq!(
+            index.headers().get("Content-Type"),
+            Some("text/html; charset=utf-8")
+        );
+        assert_eq!(body_of(index), "<h1>home</h1>");
+
+        assert!(matches!(
+            files.serve("missing.png").status(),
+            HttpStatus::NotFound
+        ));
+    }
+
+    #[test]
+    fn rejects_path_traversal() {
+        let (_dir, files) = fixture("traversal");
+
+        for path in [
+            "../secret.txt",
+            "css/../../secret.txt",
+            "/etc/passwd",
+            "..\\secret.txt",
+        ] {
+            let response = files.serve(path);
+            assert!(
+                matches!(response.status(), HttpStatus::Forbidden),
+                "{} should be forbidden",
+                path
+            );
+        }
+
+        // 🔡 path ดิบของ request ถูก decode ก่อนตรวจ
+        let (_dir, mut files) = fixture("traversal_encoded");
+        for path in [
+            "/%2e%2e/secret.txt",
+            "/%2E%2E%2Fsecret.txt",
+            "/%2fetc%2fpasswd",
+            "/..%5csecret.txt",
+        ] {
+            let raw = format!("GET {} HTTP/1.1\r\n\r\n", path);
+            let response = files.handle_request(&Request::try_from(raw.as_bytes()).unwrap());
+            assert!(
+                matches!(response.status(), HttpStatus::Forbidden),
+                "{} should be forbidden",
+                path
+            );
+        }
+    }
+}
@@ -1,17 +1,233 @@
+use crate::http::Error;
 use std::fmt::Display;
-pub enum HttpStatus {
-    Ok = 200,
-    NotFound = 404,
-    BadRequest = 400,
+
+/// 📋 ตาราง status: variant, code, reason phrase ประกาศที่เดียว
+/// แล้วสร้าง enum + `code()` + `reason()` + `from_u16()` ให้ตรงกันเสมอ
+macro_rules! http_statuses {
+    ($($(#[$doc:meta])* $variant:ident = $code:literal, $reason:literal;)+) => {
+        /// 🚦 HTTP status code ตาม IANA registry (RFC 9110 และ RFC ที่เกี่ยวข้อง)
+        /// code ที่ไม่อยู่ในตารางได้ `HttpStatus::Custom` ผ่าน `HttpStatus::from_u16`
+        ///
+        /// เทียบกันด้วย `code()`: status เดียวกันเท่ากันเสมอไม่ว่าจะสร้างมาทางไหน
+        #[derive(Debug, Clone, Copy)]
+        pub enum HttpStatus {
+            $($(#[$doc])* $variant,)+
+            /// code อื่นที่ไม่อยู่ในตาราง (reason phrase ว่าง)
+            Custom(CustomCode),
+        }
+
+        impl HttpStatus {
+            /// 🔢 status code แบบตัวเลข เช่น `404`
+            pub fn code(&self) -> u16 {
+                match self {
+                    $(Self::$variant => $code,)+
+                    Self::Custom(code) => code.0,
+                }
+            }
+
+            /// 📝 reason phrase เช่น `Not Found`
+            pub fn reason(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $reason,)+
+                    Self::Custom(_) => "",
+                }
+            }
+
+            /// 🔁 แปลงตัวเลขกลับเป็น `HttpStatus` (code ที่ไม่รู้จักได้ `Custom`)
+            ///
+            /// # Returns
+            /// * `None` เมื่อไม่ใช่เลข 3 หลัก (100..=999) ที่ status line รับได้
+            pub fn from_u16(code: u16) -> Option<Self> {
+                match code {
+                    $($code => Some(Self::$variant),)+
+                    100..=999 => Some(Self::Custom(CustomCode(code))),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// 🔢 status code นอกตาราง: เป็นเลข 3 หลักเสมอ (สร้างได้ผ่าน `HttpStatus::from_u16` เท่านั้น)
+#[derive(Debug, Clone, Copy)]
+pub struct CustomCode(u16);
+
+http_statuses! {
+    // 1xx Informational
+    Continue = 100, "Continue";
+    SwitchingProtocols = 101, "Switching Protocols";
+    Processing = 102, "Processing";
+    EarlyHints = 103, "Early Hints";
+
+    // 2xx Successful
+    Ok = 200, "OK";
+    Created = 201, "Created";
+    Accepted = 202, "Accepted";
+    NonAuthoritativeInformation = 203, "Non-Authoritative Information";
+    NoContent = 204, "No Content";
+    ResetContent = 205, "Reset Content";
+    PartialContent = 206, "Partial Content";
+    MultiStatus = 207, "Multi-Status";
+    AlreadyReported = 208, "Already Reported";
+    ImUsed = 226, "IM Used";
+
+    // 3xx Redirection
+    MultipleChoices = 300, "Multiple Choices";
+    MovedPermanently = 301, "Moved Permanently";
+    Found = 302, "Found";
+    SeeOther = 303, "See Other";
+    NotModified = 304, "Not Modified";
+    UseProxy = 305, "Use Proxy";
+    TemporaryRedirect = 307, "Temporary Redirect";
+    PermanentRedirect = 308, "Permanent Redirect";
+
+    // 4xx Client Error
+    BadRequest = 400, "Bad Request";
+    Unauthorized = 401, "Unauthorized";
+    PaymentRequired = 402, "Payment Required";
+    Forbidden = 403, "Forbidden";
+    NotFound = 404, "Not Found";
+    MethodNotAllowed = 405, "Method Not Allowed";
+    NotAcceptable = 406, "Not Acceptable";
+    ProxyAuthenticationRequired = 407, "Proxy Authentication Required";
+    RequestTimeout = 408, "Request Timeout";
+    Conflict = 409, "Conflict";
+    Gone = 410, "Gone";
+    LengthRequired = 411, "Length Required";
+    PreconditionFailed = 412, "Precondition Failed";
+    PayloadTooLarge = 413, "Content Too Large";
+    UriTooLong = 414, "URI Too Long";
+    UnsupportedMediaType = 415, "Unsupported Media Type";
+    RangeNotSatisfiable = 416, "Range Not Satisfiable";
+    ExpectationFailed = 417, "Expectation Failed";
+    MisdirectedRequest = 421, "Misdirected Request";
+    UnprocessableContent = 422, "Unprocessable Content";
+    Locked = 423, "Locked";
+    FailedDependency = 424, "Failed Dependency";
+    TooEarly = 425, "Too Early";
+    UpgradeRequired = 426, "Upgrade Required";
+    PreconditionRequired = 428, "Precondition Required";
+    TooManyRequests = 429, "Too Many Requests";
+    RequestHeaderFieldsTooLarge = 431, "Request Header Fields Too Large";
+    UnavailableForLegalReasons = 451, "Unavailable For Legal Reasons";
+
+    // 5xx Server Error
+    InternalServerError = 500, "Internal Server Error";
+    NotImplemented = 501, "Not Implemented";
+    BadGateway = 502, "Bad Gateway";
+    ServiceUnavailable = 503, "Service Unavailable";
+    GatewayTimeout = 504, "Gateway Timeout";
+    HttpVersionNotSupported = 505, "HTTP Version Not Supported";
+    VariantAlsoNegotiates = 506, "Variant Also Negotiates";
+    InsufficientStorage = 507, "Insufficient Storage";
+    LoopDetected = 508, "Loop Detected";
+    NotExtended = 510, "Not Extended";
+    NetworkAuthenticationRequired = 511, "Network Authentication Required";
+}
+
+impl HttpStatus {
+    /// 1xx
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&self.code())
+    }
+
+    /// 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.code())
+    }
+
+    /// 3xx
+    pub fn is_redirection(&self) -> bool {
+        (300..400).contains(&self.code())
+    }
+
+    /// 4xx
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.code())
+    }
+
+    /// 5xx
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.code())
+    }
+
+    /// 🚫 status ที่ห้ามมี body (1xx, 204, 304)
+    pub fn forbids_body(&self) -> bool {
+        self.is_informational() || matches!(self, Self::NoContent | Self::NotModified)
+    }
+}
+
+impl PartialEq for HttpStatus {
+    fn eq(&self, other: &Self) -> bool {
+        self.code() == other.code()
+    }
+}
+
+impl Eq for HttpStatus {}
+
+impl std::hash::Hash for HttpStatus {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.code().hash(state);
+    }
 }
 
 impl Display for HttpStatus {
+    /// เช่น `404 Not Found` (custom code ได้ `299 `: RFC 9112 บังคับ SP หลัง status code
+    /// แม้ reason phrase จะว่าง จึงใช้ต่อเป็น status line ได้ตรงๆ)
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let message = match self {
-            Self::Ok => "200 OK",
-            Self::NotFound => "404 Not Found",
-            Self::BadRequest => "400 Bad Request",
-        };
-        write!(f, "{}", message)
+        write!(f, "{} {}", self.code(), self.reason())
+    }
+}
+
+impl From<&Error> for HttpStatus {
+    /// 🚦 status ที่เหมาะกับ error ตอน parse request (อื่นๆ เป็น 400)
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::PayloadTooLarge => Self::PayloadTooLarge,
+            Error::HeaderTooLarge => Self::RequestHeaderFieldsTooLarge,
+            Error::InvalidProtocol => Self::HttpVersionNotSupported,
+            Error::UnsupportedEncoding(_) => Self::NotImplemented,
+            Error::Timeout => Self::RequestTimeout,
+            _ => Self::BadRequest,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_codes_and_reasons() {
+        assert_eq!(HttpStatus::from_u16(201), Some(HttpStatus::Created));
+        assert_eq!(
+            HttpStatus::from_u16(503).unwrap().reason(),
+            "Service Unavailable"
+        );
+        assert_eq!(HttpStatus::NotFound.to_string(), "404 Not Found");
+
+        let custom = HttpStatus::from_u16(299).unwrap();
+        assert!(matches!(custom, HttpStatus::Custom(_)));
+        assert_eq!(custom.code(), 299);
+        assert_eq!(custom.to_string(), "299 ");
+        assert_eq!(custom, HttpStatus::from_u16(299).unwrap());
+        assert_ne!(custom, HttpStatus::Ok);
+    }
+
+    #[test]
+    fn rejects_codes_outside_three_digits() {
+        assert_eq!(HttpStatus::from_u16(42), None);
+        assert_eq!(HttpStatus::from_u16(99), None);
+        assert_eq!(HttpStatus::from_u16(1000), None);
+        assert_eq!(HttpStatus::from_u16(999).map(|s| s.code()), Some(999));
+    }
+
+    #[test]
+    fn categorizes_codes() {
+        assert!(HttpStatus::NoContent.is_success());
+        assert!(HttpStatus::MovedPermanently.is_redirection());
+        assert!(HttpStatus::Unauthorized.is_client_error());
+        assert!(HttpStatus::from_u16(599).unwrap().is_server_error());
+        assert!(!HttpStatus::Ok.is_client_error());
     }
 }
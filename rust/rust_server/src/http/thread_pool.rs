@@ -0,0 +1,275 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 📦 งานที่ส่งให้ worker ทำ (เช่น จัดการ 1 connection)
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// 📬 จำนวนงานที่รอ worker ได้ต่อ 1 worker (ค่าเริ่มต้นของ `ThreadPool::new`)
+const DEFAULT_QUEUE_PER_WORKER: usize = 16;
+
+/// 🧵 ThreadPool: worker threads จำนวนคงที่ ดึงงานจาก queue เดียวกันผ่าน `mpsc`
+///
+/// queue มีขนาดจำกัด: เต็มแล้ว `execute` จะรอจนมีที่ว่าง
+/// (เช็คก่อนด้วย `is_full` ถ้าอยากปฏิเสธงานแทนการรอ)
+///
+/// เมื่อ drop: ปิด channel ก่อน (worker จะออกจาก loop หลังทำงานที่ค้างเสร็จ)
+/// แล้วรอ join ทุก worker
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: Option<mpsc::SyncSender<Job>>,
+    queued: Arc<AtomicUsize>, // 📬 งานที่ส่งเข้า queue แล้วแต่ยังไม่มี worker รับ
+    idle: Arc<AtomicUsize>,   // 💤 worker ที่ว่างรองานอยู่
+    capacity: usize,
+}
+
+impl ThreadPool {
+    /// 🏗️ สร้าง pool ที่มี worker `size` ตัว (queue รับได้ 16 งานต่อ worker)
+    ///
+    /// # Panics
+    /// * เมื่อ `size` เป็น 0
+    pub fn new(size: usize) -> ThreadPool {
+        Self::with_queue_capacity(size, size.saturating_mul(DEFAULT_QUEUE_PER_WORKER))
+    }
+
+    /// 🏗️ สร้าง pool ที่มี worker `size` ตัว และมีงานรอใน queue ได้ไม่เกิน `capacity`
+    ///
+    /// `capacity` ไม่รวมงานที่ worker ว่างรับไปทำได้ทันที (0 = ไม่มีงานรอเกินจำนวน worker ที่ว่าง)
+    ///
+    /// # Panics
+    /// * เมื่อ `size` เป็น 0
+    pub fn with_queue_capacity(size: usize, capacity: usize) -> ThreadPool {
+        assert!(size > 0, "thread pool needs at least one worker");
+
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        // 🔐 receiver ใช้ร่วมกันทุก worker: Arc สำหรับแชร์, Mutex ให้รับงานทีละตัว
+        let receiver = Arc::new(Mutex::new(receiver));
+        let queued = Arc::new(AtomicUsize::new(0));
+        let idle = Arc::new(AtomicUsize::new(size));
+
+        let workers = (0..size)
+            .map(|id| {
+                Worker::new(
+                    id,
+                    Arc::clone(&receiver),
+                    Arc::clone(&queued),
+                    Arc::clone(&idle),
+                )
+            })
+            .collect();
+
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+            queued,
+            idle,
+            capacity,
+        }
+    }
+
+    /// 📤 ส่งงานเข้า queue ให้ worker ที่ว่างตัวถัดไปทำ (รอถ้า queue เต็ม)
+    pub fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if let Some(sender) = &self.sender {
+            self.queued.fetch_add(1, Ordering::SeqCst);
+            // send ล้มเหลวได้เฉพาะตอนที่ทุก worker ตายไปแล้ว
+            if sender.send(Box::new(job)).is_err() {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// 📬 จำนวนงานที่รอ worker อยู่ใน queue
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// 🚧 queue เต็มหรือไม่ (`execute` ตอนนี้จะต้องรอ)
+    ///
+    /// worker ที่ว่างอยู่รับงานได้โดยไม่ใช้ที่ใน queue จึงนับรวมด้วย
+    pub fn is_full(&self) -> bool {
+        self.queued() >= self.capacity + self.idle.load(Ordering::SeqCst)
+    }
+
+    /// 📬 ตัวนับงานที่รออยู่ แชร์ให้ worker ดูได้ว่ามีงานอื่นรออยู่ไหม
+    pub(crate) fn queued_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.queued)
+    }
+
+    /// ⏱️ ปิด pool แบบมี deadline: ให้ worker ทำงานที่ค้างอยู่ให้เสร็จภายใน `timeout`
+    ///
+    /// # Returns
+    /// * `true` - ทุก worker จบงานและ join แล้ว
+    /// * `false` - เลย deadline: worker ที่ยังทำงานอยู่จะถูกปล่อย (detach) ไป
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> bool {
+        drop(self.sender.take());
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let finished = self
+                .workers
+                .iter()
+                .all(|worker| worker.thread.as_ref().is_none_or(|t| t.is_finished()));
+            if finished || Instant::now() >= deadline {
+                break;
+            }
+            thread::sleep(SHUTDOWN_POLL_INTERVAL);
+        }
+
+        let mut all_finished = true;
+        for worker in &mut self.workers {
+            match worker.thread.take() {
+                Some(thread) if thread.is_finished() => {
+                    let _ = thread.join();
+                }
+                Some(_) => all_finished = false, // JoinHandle ถูก drop = detach
+                None => {}
+            }
+        }
+        all_finished
+    }
+}
+
+/// 🔁 ความถี่ในการเช็คว่า worker จบงานแล้วหรือยังระหว่าง shutdown
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // 🚪 ปิด channel: `recv()` ของทุก worker จะคืน Err หลัง queue ว่าง
+        drop(self.sender.take());
+
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take()
+                && thread.join().is_err()
+            {
+                eprintln!("⚠️ Worker {} exited with a panic", worker.id);
+            }
+        }
+    }
+}
+
+struct Worker {
+    id: usize,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(
+        id: usize,
+        receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+        queued: Arc<AtomicUsize>,
+        idle: Arc<AtomicUsize>,
+    ) -> Worker {
+        let thread = thread::spawn(move || {
+            loop {
+                // lock ถูกปล่อยทันทีหลัง recv() เพื่อให้ worker อื่นรับงานได้ระหว่างที่ตัวนี้ทำงาน
+                let message = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => break,
+                };
+
+                let job = match message {
+                    Ok(job) => job,
+                    Err(_) => break, // 🛑 channel ปิดแล้ว
+                };
+                idle.fetch_sub(1, Ordering::SeqCst);
+                queued.fetch_sub(1, Ordering::SeqCst);
+
+                // 🛡️ job ที่ panic ไม่ควรทำให้ pool เสีย worker ไปถาวร
+                if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                    eprintln!("⚠️ Worker {} recovered from a panicking job", id);
+                }
+                idle.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        Worker {
+            id,
+            thread: Some(thread),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+
+    #[test]
+    fn runs_jobs_concurrently_and_joins_on_drop() {
+        let pool = ThreadPool::new(4);
+        // 🚧 ถ้า job ไม่ได้รันพร้อมกันครบ 4 ตัว barrier จะค้างตลอดไป
+        let barrier = Arc::new(Barrier::new(4));
+        let done = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..4 {
+            let barrier = Arc::clone(&barrier);
+            let done = Arc::clone(&done);
+            pool.execute(move || {
+                barrier.wait();
+                done.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+
+        assert_eq!(done.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn shutdown_timeout_detaches_stuck_workers() {
+        let pool = ThreadPool::new(2);
+        pool.execute(|| thread::sleep(Duration::from_millis(10)));
+        pool.execute(|| thread::sleep(Duration::from_secs(5)));
+
+        let started = Instant::now();
+        assert!(!pool.shutdown_timeout(Duration::from_millis(200)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn counts_queued_jobs_against_capacity() {
+        let pool = ThreadPool::with_queue_capacity(1, 2);
+        let (release, blocked) = mpsc::channel::<()>();
+        let (started, running) = mpsc::channel();
+        pool.execute(move || {
+            started.send(()).unwrap();
+            let _ = blocked.recv();
+        });
+        running.recv().unwrap(); // 🧵 worker เดียวถูกใช้อยู่: งานถัดไปต้องรอใน queue
+
+        pool.execute(|| {});
+        assert!(!pool.is_full());
+        pool.execute(|| {});
+        assert_eq!(pool.queued(), 2);
+        assert!(pool.is_full());
+
+        drop(release);
+        drop(pool);
+    }
+
+    #[test]
+    fn zero_capacity_still_accepts_jobs_for_idle_workers() {
+        let pool = ThreadPool::with_queue_capacity(1, 0);
+        assert!(!pool.is_full());
+
+        let (release, blocked) = mpsc::channel::<()>();
+        let (started, running) = mpsc::channel();
+        pool.execute(move || {
+            started.send(()).unwrap();
+            let _ = blocked.recv();
+        });
+        running.recv().unwrap();
+        assert!(pool.is_full()); // 🧵 worker เดียวไม่ว่างแล้ว และไม่มีที่รอใน queue
+
+        drop(release);
+        drop(pool);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::http::server::Connection;
+use crate::http::{Error, Result};
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use rustls::{ServerConfig, ServerConnection, StreamOwned};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// ⏱️ เวลาที่รอเขียน `close_notify` ตอนปิด (peer ที่ไม่อ่านแล้วต้องไม่ค้าง worker ใน `drop`)
+const CLOSE_NOTIFY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// 🔐 TlsConfig: certificate ของ HTTPS server (เปิดด้วย cargo feature `tls`)
+///
+/// มี certificate หลัก 1 ชุด และเพิ่ม certificate ตามชื่อ host ได้ (SNI)
+/// client ที่ขอชื่อที่ไม่รู้จัก (หรือไม่ส่ง SNI มา) ได้ certificate หลัก
+///
+/// ```no_run
+/// use rust_server::http::{Router, Server, TlsConfig};
+///
+/// let tls = TlsConfig::from_pem_files("certs/default.pem", "certs/default.key")?
+///     .with_sni_pem_files("api.example.com", "certs/api.pem", "certs/api.key")?;
+/// let server = Server::new("0.0.0.0:8443".to_string(), Router::new()).with_tls(tls);
+/// # Ok::<(), rust_server::http::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    default: Arc<CertifiedKey>,
+    by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl TlsConfig {
+    /// 📄 certificate chain และ private key จากไฟล์ PEM
+    pub fn from_pem_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(load_pem_files(cert_path, key_path)?))
+    }
+
+    /// 📄 certificate chain และ private key จาก PEM ใน memory
+    pub fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        Ok(Self::new(load_pem(cert_pem, key_pem)?))
+    }
+
+    fn new(default: CertifiedKey) -> Self {
+        Self {
+            default: Arc::new(default),
+            by_name: HashMap::new(),
+        }
+    }
+
+    /// 🏷️ ใช้ certificate นี้เมื่อ client ขอ `server_name` (รองรับ `*.example.com`)
+    pub fn with_sni_pem_files(
+        self,
+        server_name: &str,
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        Ok(self.with_certified_key(server_name, load_pem_files(cert_path, key_path)?))
+    }
+
+    /// 🏷️ เหมือน `with_sni_pem_files` แต่รับ PEM จาก memory
+    pub fn with_sni_pem(self, server_name: &str, cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        Ok(self.with_certified_key(server_name, load_pem(cert_pem, key_pem)?))
+    }
+
+    fn with_certified_key(mut self, server_name: &str, key: CertifiedKey) -> Self {
+        self.by_name
+            .insert(server_name.to_ascii_lowercase(), Arc::new(key));
+        self
+    }
+
+    /// ⚙️ `ServerConfig` ของ rustls ที่ server ใช้กับทุก connection
+    pub(crate) fn server_config(&self) -> Result<Arc<ServerConfig>> {
+        let mut config = ServerConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_cert_resolver(Arc::new(self.clone()));
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        Ok(Arc::new(config))
+    }
+
+    /// 🔎 certificate ของชื่อ host: ตรงตัวก่อน แล้วค่อย wildcard 1 ระดับ
+    fn lookup(&self, server_name: &str) -> Option<&Arc<CertifiedKey>> {
+        let server_name = server_name.to_ascii_lowercase();
+        self.by_name.get(&server_name).or_else(|| {
+            let (_, parent) = server_name.split_once('.')?;
+            self.by_name.get(&format!("*.{}", parent))
+        })
+    }
+}
+
+impl ResolvesServerCert for TlsConfig {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let key = client_hello
+            .server_name()
+            .and_then(|name| self.lookup(name))
+            .unwrap_or(&self.default);
+        Some(Arc::clone(key))
+    }
+}
+
+/// 🤝 ทำ TLS handshake บน connection ที่เพิ่ง accept มา
+///
+/// handshake ใช้ read timeout ของ `stream` เหมือน request ปกติ
+/// client ที่เงียบไปกลางทางจึงไม่ค้าง worker ไว้ตลอด
+pub(crate) fn accept(config: &Arc<ServerConfig>, mut stream: TcpStream) -> Result<TlsStream> {
+    let mut connection = ServerConnection::new(Arc::clone(config))?;
+    while connection.is_handshaking() {
+        connection.complete_io(&mut stream)?;
+    }
+    Ok(TlsStream(StreamOwned::new(connection, stream)))
+}
+
+/// 🔐 TLS connection ฝั่ง server: ส่ง `close_notify` ก่อนปิดเสมอ
+/// (ทั้งตอนจบ keep-alive และหลัง upgrade) client จึงแยกออกได้ว่าข้อมูลครบ ไม่ได้ถูกตัดกลางทาง
+pub(crate) struct TlsStream(StreamOwned<ServerConnection, TcpStream>);
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl Connection for TlsStream {
+    fn socket(&self) -> &TcpStream {
+        &self.0.sock
+    }
+}
+
+impl Drop for TlsStream {
+    fn drop(&mut self) {
+        self.0.conn.send_close_notify();
+        // 🔌 best-effort: client อาจปิดหรือเลิกอ่านไปแล้ว ส่งไม่ทันก็ไม่เป็นไร
+        let _ = self.0.sock.set_write_timeout(Some(CLOSE_NOTIFY_TIMEOUT));
+        let _ = self.0.flush();
+    }
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn load_pem_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> Result<CertifiedKey> {
+    let (cert_path, key_path) = (cert_path.as_ref(), key_path.as_ref());
+    let pem_error = |path: &Path, e| Error::Tls(format!("{}: {}", path.display(), e));
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(Iterator::collect)
+        .map_err(|e| pem_error(cert_path, e))?;
+    let key = PrivateKeyDer::from_pem_file(key_path).map_err(|e| pem_error(key_path, e))?;
+    certified_key(certs, key)
+}
+
+fn load_pem(cert_pem: &[u8], key_pem: &[u8]) -> Result<CertifiedKey> {
+    let certs = CertificateDer::pem_slice_iter(cert_pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::Tls(format!("certificate: {}", e)))?;
+    let key =
+        PrivateKeyDer::from_pem_slice(key_pem).map_err(|e| Error::Tls(format!("key: {}", e)))?;
+    certified_key(certs, key)
+}
+
+/// 🔗 จับคู่ certificate chain กับ key (และตรวจว่าเป็นคู่กันจริง)
+fn certified_key(
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+) -> Result<CertifiedKey> {
+    if certs.is_empty() {
+        return Err(Error::Tls("no certificate found in PEM".to_string()));
+    }
+    Ok(CertifiedKey::from_der(certs, key, &provider())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustls::pki_types::ServerName;
+    use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// 🧪 self-signed certificate สร้างใหม่ทุกครั้งที่รัน test
+    fn self_signed(name: &str) -> (String, String) {
+        let cert = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+        (cert.cert.pem(), cert.key_pair.serialize_pem())
+    }
+
+    fn client(trusted: &str, server_name: &str, addr: std::net::SocketAddr) -> String {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_slice_iter(trusted.as_bytes()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let config = ClientConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let name = ServerName::try_from(server_name.to_string()).unwrap();
+        let connection = ClientConnection::new(Arc::new(config), name).unwrap();
+
+        let mut stream = StreamOwned::new(connection, TcpStream::connect(addr).unwrap());
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut response = Vec::new();
+        // 🔐 server ส่ง close_notify ก่อนปิด: `read_to_end` จบแบบไม่มี error
+        stream.read_to_end(&mut response).unwrap();
+        String::from_utf8(response).unwrap()
+    }
+
+    #[test]
+    fn serves_https_and_selects_certificate_by_sni() {
+        use crate::http::{HttpStatus, Response, Router, Server};
+
+        let (default_cert, default_key) = self_signed("localhost");
+        let (api_cert, api_key) = self_signed("api.test");
+        let tls = TlsConfig::from_pem(default_cert.as_bytes(), default_key.as_bytes())
+            .unwrap()
+            .with_sni_pem("*.test", api_cert.as_bytes(), api_key.as_bytes())
+            .unwrap();
+
+        let router = Router::new().get("/", |_| {
+            Response::new(HttpStatus::Ok, Some("secure".to_string()))
+        });
+        let server = Server::new("127.0.0.1:0".to_string(), router)
+            .with_tls(tls)
+            .without_access_log();
+        let shutdown = server.shutdown_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        // ✅ client เชื่อเฉพาะ certificate ที่ตรงกับชื่อที่ขอ: handshake ผ่านแปลว่าเลือกถูกใบ
+        assert!(client(&default_cert, "localhost", addr).ends_with("\r\n\r\nsecure"));
+        assert!(client(&api_cert, "api.test", addr).ends_with("\r\n\r\nsecure"));
+
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn rejects_mismatched_key_and_missing_files() {
+        let (cert, _) = self_signed("localhost");
+        let (_, other_key) = self_signed("localhost");
+
+        assert!(TlsConfig::from_pem(cert.as_bytes(), other_key.as_bytes()).is_err());
+        assert!(TlsConfig::from_pem(b"", other_key.as_bytes()).is_err());
+        assert!(TlsConfig::from_pem_files("missing.pem", "missing.key").is_err());
+    }
+}
@@ -0,0 +1,616 @@
+use crate::http::server::Connection;
+use crate::http::{Error, HttpStatus, Method, Request, Response, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha1::{Digest, Sha1};
+use std::io::{Read, Write};
+use std::time::Duration;
+
+/// 🔑 GUID ที่ต่อท้าย `Sec-WebSocket-Key` ก่อน hash (RFC 6455 §1.3)
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// 📏 ขนาด message สูงสุดที่รับ (รวมทุก fragment) เกินแล้วปิดด้วย 1009
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// 🚪 close codes ที่ server ส่งเองเมื่อ client ทำผิด (RFC 6455 §7.4.1)
+const CLOSE_NORMAL: u16 = 1000;
+const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+const CLOSE_INVALID_DATA: u16 = 1007;
+const CLOSE_TOO_BIG: u16 = 1009;
+
+/// 🤝 ตอบ WebSocket handshake: 101 แล้วเรียก `on_upgrade` บน connection เดิม
+///
+/// ตรวจ `GET` + `Upgrade: websocket` + `Connection: Upgrade` + `Sec-WebSocket-Key`
+/// ที่ถูกต้อง ไม่ผ่านได้ 400 ส่วน `Sec-WebSocket-Version` ที่ไม่ใช่ 13 ได้ 426
+///
+/// `on_upgrade` ทำงานบน worker thread ของ connection นั้นจนกว่าจะ return
+/// (ใช้ได้กับ `Server` เท่านั้น `AsyncServer` ตอบ 501)
+///
+/// ```
+/// use rust_server::http::{Router, websocket::{self, Message}};
+///
+/// let router = Router::new().get("/echo", |req| {
+///     websocket::upgrade(req, |mut socket| {
+///         // 🔁 echo ทุก message จนกว่า client จะปิด
+///         while let Ok(message) = socket.read() {
+///             let echo = match message {
+///                 Message::Text(_) | Message::Binary(_) => message,
+///                 Message::Close(_) => break,
+///                 _ => continue, // 🏓 ping ถูกตอบ pong ให้แล้ว
+///             };
+///             if socket.send(echo).is_err() {
+///                 break;
+///             }
+///         }
+///     })
+/// });
+/// ```
+pub fn upgrade(request: &Request, on_upgrade: impl FnOnce(WebSocket) + Send + 'static) -> Response {
+    let headers = request.headers();
+    if request.method() != &Method::GET
+        || !headers.contains_token("Upgrade", "websocket")
+        || !headers.contains_token("Connection", "upgrade")
+    {
+        return Response::new(
+            HttpStatus::BadRequest,
+            Some("expected a WebSocket upgrade request".to_string()),
+        );
+    }
+
+    if headers.get("Sec-WebSocket-Version").map(str::trim) != Some("13") {
+        return Response::new(HttpStatus::UpgradeRequired, None)
+            .with_header("Sec-WebSocket-Version", "13");
+    }
+
+    // 🔑 key ต้องเป็น base64 ของ 16 bytes
+    let key = headers.get("Sec-WebSocket-Key").map(str::trim);
+    let Some(key) = key.filter(|key| BASE64.decode(key).is_ok_and(|nonce| nonce.len() == 16))
+    else {
+        return Response::new(
+            HttpStatus::BadRequest,
+            Some("invalid Sec-WebSocket-Key".to_string()),
+        );
+    };
+
+    Response::builder(HttpStatus::SwitchingProtocols)
+        .header("Upgrade", "websocket")
+        .header("Connection", "Upgrade")
+        .header("Sec-WebSocket-Accept", accept_key(key))
+        .build()
+        .with_upgrade(move |connection| on_upgrade(WebSocket::new(connection)))
+}
+
+/// 🔑 `Sec-WebSocket-Accept` = base64(SHA-1(key + GUID))
+pub fn accept_key(key: &str) -> String {
+    let mut sha1 = Sha1::new();
+    sha1.update(key.as_bytes());
+    sha1.update(ACCEPT_GUID.as_bytes());
+    BASE64.encode(sha1.finalize())
+}
+
+/// 🏷️ ชนิดของ frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl Opcode {
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0x0 => Some(Opcode::Continuation),
+            0x1 => Some(Opcode::Text),
+            0x2 => Some(Opcode::Binary),
+            0x8 => Some(Opcode::Close),
+            0x9 => Some(Opcode::Ping),
+            0xA => Some(Opcode::Pong),
+            _ => None,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        match self {
+            Opcode::Continuation => 0x0,
+            Opcode::Text => 0x1,
+            Opcode::Binary => 0x2,
+            Opcode::Close => 0x8,
+            Opcode::Ping => 0x9,
+            Opcode::Pong => 0xA,
+        }
+    }
+
+    /// close/ping/pong: ห้าม fragment และ payload ไม่เกิน 125 bytes
+    pub fn is_control(self) -> bool {
+        matches!(self, Opcode::Close | Opcode::Ping | Opcode::Pong)
+    }
+}
+
+/// 🧱 Frame: หน่วยเล็กสุดบนสาย (RFC 6455 §5.2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub fin: bool, // 🏁 frame สุดท้ายของ message
+    pub opcode: Opcode,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(opcode: Opcode, payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            fin: true,
+            opcode,
+            payload: payload.into(),
+        }
+    }
+
+    /// 📥 อ่าน frame จาก client: ต้อง mask เสมอ, RSV ต้องเป็น 0 และ control frame ห้าม fragment
+    ///
+    /// payload ที่ใหญ่กว่า `max_payload` ได้ `Error::PayloadTooLarge` (ก่อนจะจอง memory)
+    pub fn read_from<R: Read>(reader: &mut R, max_payload: usize) -> Result<Frame> {
+        let mut head = [0; 2];
+        reader.read_exact(&mut head)?;
+
+        let fin = head[0] & 0x80 != 0;
+        if head[0] & 0x70 != 0 {
+            return Err(protocol_error("reserved bits set without extension"));
+        }
+        let opcode =
+            Opcode::from_bits(head[0] & 0x0F).ok_or_else(|| protocol_error("unknown opcode"))?;
+        if head[1] & 0x80 == 0 {
+            return Err(protocol_error("client frames must be masked"));
+        }
+
+        let length = match head[1] & 0x7F {
+            126 => {
+                let mut length = [0; 2];
+                reader.read_exact(&mut length)?;
+                u16::from_be_bytes(length) as u64
+            }
+            127 => {
+                let mut length = [0; 8];
+                reader.read_exact(&mut length)?;
+                u64::from_be_bytes(length)
+            }
+            length => length as u64,
+        };
+        if opcode.is_control() && (!fin || length > 125) {
+            return Err(protocol_error(
+                "control frames must be whole and at most 125 bytes",
+            ));
+        }
+        if length > max_payload as u64 {
+            return Err(Error::PayloadTooLarge);
+        }
+
+        let mut mask = [0; 4];
+        reader.read_exact(&mut mask)?;
+        let mut payload = vec![0; length as usize];
+        reader.read_exact(&mut payload)?;
+        apply_mask(&mut payload, mask);
+
+        Ok(Frame {
+            fin,
+            opcode,
+            payload,
+        })
+    }
+
+    /// 📤 เขียน frame ฝั่ง server (ไม่ mask)
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.write(writer, None)
+    }
+
+    /// 📤 เขียน frame ฝั่ง client (mask ด้วย `key`)
+    pub fn write_masked<W: Write>(&self, writer: &mut W, key: [u8; 4]) -> std::io::Result<()> {
+        self.write(writer, Some(key))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, mask: Option<[u8; 4]>) -> std::io::Result<()> {
+        let mut frame = Vec::with_capacity(self.payload.len() + 14);
+        frame.push(if self.fin { 0x80 } else { 0 } | self.opcode.bits());
+
+        let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+        match self.payload.len() {
+            length @ 0..=125 => frame.push(mask_bit | length as u8),
+            length @ 126..=0xFFFF => {
+                frame.push(mask_bit | 126);
+                frame.extend_from_slice(&(length as u16).to_be_bytes());
+            }
+            length => {
+                frame.push(mask_bit | 127);
+                frame.extend_from_slice(&(length as u64).to_be_bytes());
+            }
+        }
+
+        let start = frame.len();
+        if let Some(key) = mask {
+            frame.extend_from_slice(&key);
+        }
+        frame.extend_from_slice(&self.payload);
+        if let Some(key) = mask {
+            apply_mask(&mut frame[start + 4..], key);
+        }
+
+        writer.write_all(&frame)?;
+        writer.flush()
+    }
+}
+
+/// 🎭 XOR payload กับ masking key (ใช้ทั้ง mask และ unmask)
+fn apply_mask(payload: &mut [u8], key: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= key[i % 4];
+    }
+}
+
+/// 🚦 close code ที่ส่งบนสายได้ (RFC 6455 §7.4): 1005/1006/1015 ใช้ภายในเท่านั้น
+/// 1016-2999 ยังไม่มีการกำหนด 3000-4999 สำหรับ library และ application
+fn is_valid_close_code(code: u16) -> bool {
+    matches!(code, 1000..=1003 | 1007..=1014 | 3000..=4999)
+}
+
+fn protocol_error(reason: &str) -> Error {
+    Error::WebSocket(reason.to_string())
+}
+
+/// 💬 message ที่ประกอบจาก frame แล้ว
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    /// 🚪 close code และเหตุผล (`None` เมื่อ client ไม่ได้ส่ง code มา)
+    Close(Option<(u16, String)>),
+}
+
+/// 🔌 WebSocket: connection หลัง handshake ที่ handler อ่าน/เขียน message ได้
+///
+/// * ประกอบ fragment เป็น message เดียว (แทรก control frame ระหว่างกลางได้)
+/// * ตอบ Ping ด้วย Pong ให้อัตโนมัติ (แต่ยังคืน `Message::Ping` ให้ handler เห็น)
+/// * ตอบ Close กลับให้อัตโนมัติ หลังจากนั้นส่งอะไรต่อไม่ได้แล้ว
+/// * client ทำผิด protocol ได้ close frame (1002/1007/1009) แล้วคืน `Err`
+/// * ใช้ worker ของ server ไปตลอดที่เปิดอยู่: client ที่เงียบเกิน
+///   `Server::with_upgrade_timeout` ทำให้ `read` ได้ `Error::Timeout`
+pub struct WebSocket {
+    stream: Box<dyn Connection>,
+    max_message_size: usize,
+    fragments: Option<(Opcode, Vec<u8>)>, // 🧩 message ที่ยังได้ fragment ไม่ครบ
+    closed: bool,                         // 🚪 ส่ง close frame ไปแล้ว
+}
+
+impl WebSocket {
+    pub(crate) fn new(stream: Box<dyn Connection>) -> Self {
+        Self {
+            stream,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            fragments: None,
+            closed: false,
+        }
+    }
+
+    /// 📏 ขนาด message สูงสุดที่รับ (ค่าเริ่มต้น: 1 MiB)
+    pub fn set_max_message_size(&mut self, max_message_size: usize) {
+        self.max_message_size = max_message_size;
+    }
+
+    /// 💤 เวลาที่ `read` รอได้ก่อนได้ `Error::Timeout`
+    /// (ค่าเริ่มต้น: `Server::with_upgrade_timeout` และ `None` คือรอไม่จำกัด)
+    ///
+    /// timeout อาจเกิดกลาง frame: ได้ `Error::Timeout` แล้วควรปิด connection
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.stream.socket().set_read_timeout(timeout)?)
+    }
+
+    /// 📥 อ่าน message ถัดไป
+    pub fn read(&mut self) -> Result<Message> {
+        loop {
+            let buffered = self.fragments.as_ref().map_or(0, |(_, data)| data.len());
+            let max_payload = self.max_message_size.saturating_sub(buffered);
+            let frame = match Frame::read_from(&mut self.stream, max_payload) {
+                Ok(frame) => frame,
+                Err(e) => return Err(self.fail(e)),
+            };
+
+            let (opcode, payload) = match (frame.opcode, self.fragments.take()) {
+                (Opcode::Ping, fragments) => {
+                    self.fragments = fragments;
+                    self.write_frame(Frame::new(Opcode::Pong, frame.payload.clone()))?;
+                    return Ok(Message::Ping(frame.payload));
+                }
+                (Opcode::Pong, fragments) => {
+                    self.fragments = fragments;
+                    return Ok(Message::Pong(frame.payload));
+                }
+                (Opcode::Close, _) => return self.on_close(&frame.payload),
+                (Opcode::Text | Opcode::Binary, None) => (frame.opcode, frame.payload),
+                (Opcode::Continuation, Some((opcode, mut data))) => {
+                    data.extend_from_slice(&frame.payload);
+                    (opcode, data)
+                }
+                (Opcode::Continuation, None) => {
+                    return Err(self.fail(protocol_error("continuation without a message")));
+                }
+                (_, Some(_)) => {
+                    return Err(self.fail(protocol_error("new message before the last one ended")));
+                }
+            };
+
+            if !frame.fin {
+                self.fragments = Some((opcode, payload));
+                continue;
+            }
+            return match opcode {
+                Opcode::Text => match String::from_utf8(payload) {
+                    Ok(text) => Ok(Message::Text(text)),
+                    Err(e) => Err(self.fail(Error::Utf8(e.to_string()))),
+                },
+                _ => Ok(Message::Binary(payload)),
+            };
+        }
+    }
+
+    /// 📤 ส่ง message (ส่ง `Message::Close` แล้วส่งอะไรต่อไม่ได้)
+    pub fn send(&mut self, message: Message) -> Result<()> {
+        let frame = match message {
+            Message::Text(text) => Frame::new(Opcode::Text, text),
+            Message::Binary(data) => Frame::new(Opcode::Binary, data),
+            Message::Ping(data) => Frame::new(Opcode::Ping, data),
+            Message::Pong(data) => Frame::new(Opcode::Pong, data),
+            Message::Close(close) => {
+                let (code, reason) = close.unwrap_or((CLOSE_NORMAL, String::new()));
+                return self.close(code, &reason);
+            }
+        };
+        if frame.opcode.is_control() && frame.payload.len() > 125 {
+            return Err(protocol_error("control frames must be at most 125 bytes"));
+        }
+        self.write_frame(frame)
+    }
+
+    /// 🚪 ส่ง close frame (ครั้งเดียว ครั้งต่อไปไม่ทำอะไร)
+    pub fn close(&mut self, code: u16, reason: &str) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let mut payload = code.to_be_bytes().to_vec();
+        // ✂️ reason ยาวได้ไม่เกิน 123 bytes (ตัดที่ขอบตัวอักษร)
+        let mut end = reason.len().min(123);
+        while !reason.is_char_boundary(end) {
+            end -= 1;
+        }
+        payload.extend_from_slice(&reason.as_bytes()[..end]);
+
+        self.write_frame(Frame::new(Opcode::Close, payload))?;
+        self.closed = true;
+        Ok(())
+    }
+
+    fn write_frame(&mut self, frame: Frame) -> Result<()> {
+        if self.closed {
+            return Err(protocol_error("connection already closed"));
+        }
+        Ok(frame.write_to(&mut self.stream)?)
+    }
+
+    /// 🚪 client ขอปิด: ตอบ close กลับด้วย code เดียวกัน (ถ้าเรายังไม่ได้ปิดก่อน)
+    fn on_close(&mut self, payload: &[u8]) -> Result<Message> {
+        let close = match payload {
+            [] => None,
+            [_] => return Err(self.fail(protocol_error("close payload of one byte"))),
+            [high, low, reason @ ..] => {
+                let reason = match std::str::from_utf8(reason) {
+                    Ok(reason) => reason.to_string(),
+                    Err(e) => return Err(self.fail(Error::Utf8(e.to_string()))),
+                };
+                let code = u16::from_be_bytes([*high, *low]);
+                if !is_valid_close_code(code) {
+                    let reason = format!("invalid close code {}", code);
+                    return Err(self.fail(protocol_error(&reason)));
+                }
+                Some((code, reason))
+            }
+        };
+
+        let code = close.as_ref().map_or(CLOSE_NORMAL, |(code, _)| *code);
+        self.close(code, "")?;
+        Ok(Message::Close(close))
+    }
+
+    /// ❌ ปิด connection ด้วย close code ที่ตรงกับ error แล้วคืน error เดิม
+    fn fail(&mut self, error: Error) -> Error {
+        let code = match error {
+            Error::PayloadTooLarge => CLOSE_TOO_BIG,
+            Error::Utf8(_) => CLOSE_INVALID_DATA,
+            Error::WebSocket(_) => CLOSE_PROTOCOL_ERROR,
+            _ => return error, // 🔌 I/O error: ส่ง close ไม่ได้อยู่แล้ว
+        };
+        let _ = self.close(code, "");
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Router, Server};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    const KEY: [u8; 4] = [0x37, 0xfa, 0x21, 0x3d];
+
+    fn handshake(headers: &str) -> Request {
+        let raw = format!("GET /ws HTTP/1.1\r\n{}\r\n", headers);
+        Request::try_from(raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn computes_accept_key_and_validates_handshake() {
+        // 📚 ตัวอย่างจาก RFC 6455 §1.3
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+
+        let valid = "Upgrade: websocket\r\nConnection: keep-alive, Upgrade\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n";
+        let response = upgrade(&handshake(valid), |_| {});
+        assert_eq!(response.status(), &HttpStatus::SwitchingProtocols);
+        assert_eq!(
+            response.headers().get("Sec-WebSocket-Accept"),
+            Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+        );
+
+        let old_version = valid.replace("Version: 13", "Version: 8");
+        let response = upgrade(&handshake(&old_version), |_| {});
+        assert_eq!(response.status(), &HttpStatus::UpgradeRequired);
+        assert_eq!(response.headers().get("Sec-WebSocket-Version"), Some("13"));
+
+        let short_key = valid.replace("dGhlIHNhbXBsZSBub25jZQ==", "c2hvcnQ=");
+        let response = upgrade(&handshake(&short_key), |_| {});
+        assert_eq!(response.status(), &HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn round_trips_frames_and_rejects_unmasked_client_frames() {
+        let frame = Frame::new(Opcode::Binary, vec![7; 300]);
+        let mut masked = Vec::new();
+        frame.write_masked(&mut masked, KEY).unwrap();
+        assert_eq!(&masked[..4], &[0x82, 0x80 | 126, 0x01, 0x2c]);
+        assert_eq!(Frame::read_from(&mut &masked[..], 1024).unwrap(), frame);
+        assert!(matches!(
+            Frame::read_from(&mut &masked[..], 100),
+            Err(Error::PayloadTooLarge)
+        ));
+
+        let mut unmasked = Vec::new();
+        frame.write_to(&mut unmasked).unwrap();
+        assert!(matches!(
+            Frame::read_from(&mut &unmasked[..], 1024),
+            Err(Error::WebSocket(_))
+        ));
+    }
+
+    #[test]
+    fn echoes_fragmented_messages_over_upgraded_connection() {
+        let router = Router::new().get("/ws", websocket_echo);
+        let server = Server::new("127.0.0.1:0".to_string(), router).without_access_log();
+        let shutdown = server.shutdown_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        let mut stream = connect(addr);
+        // 🧩 "Hel" + ping แทรกกลาง + "lo" แล้วปิด
+        let frames = [
+            Frame {
+                fin: false,
+                opcode: Opcode::Text,
+                payload: b"Hel".to_vec(),
+            },
+            Frame::new(Opcode::Ping, "?"),
+            Frame {
+                fin: true,
+                opcode: Opcode::Continuation,
+                payload: b"lo".to_vec(),
+            },
+            Frame::new(Opcode::Close, CLOSE_NORMAL.to_be_bytes()),
+        ];
+        for frame in &frames {
+            frame.write_masked(&mut stream, KEY).unwrap();
+        }
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        let head_end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+        let head = String::from_utf8_lossy(&response[..head_end]);
+        assert!(head.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+        assert!(head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+        // 📥 frame จาก server ไม่ mask: อ่านด้วยการ parse header เอง
+        let frames = &response[head_end..];
+        assert_eq!(&frames[..3], &[0x8A, 1, b'?']); // 🏓 pong
+        assert_eq!(&frames[3..10], &[0x81, 5, b'H', b'e', b'l', b'l', b'o']);
+        assert_eq!(&frames[10..], &[0x88, 2, 0x03, 0xe8]); // 🚪 close 1000
+
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn rejects_reserved_and_out_of_range_close_codes() {
+        for code in [1000, 1003, 1007, 1014, 3000, 4999] {
+            assert!(is_valid_close_code(code), "{}", code);
+        }
+        for code in [0, 999, 1004, 1005, 1006, 1015, 2999, 5000] {
+            assert!(!is_valid_close_code(code), "{}", code);
+        }
+
+        let router = Router::new().get("/ws", websocket_echo);
+        let server = Server::new("127.0.0.1:0".to_string(), router).without_access_log();
+        let shutdown = server.shutdown_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        // 🚫 1005 ห้ามอยู่บนสาย: server ต้องปิดด้วย 1002 ไม่ใช่ echo 1005 กลับ
+        let mut stream = connect(addr);
+        Frame::new(Opcode::Close, 1005u16.to_be_bytes())
+            .write_masked(&mut stream, KEY)
+            .unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        let head_end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 4;
+        assert_eq!(&response[head_end..], &[0x88, 2, 0x03, 0xea]); // 🚪 close 1002
+
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn closes_silent_connections_after_upgrade_timeout() {
+        let router = Router::new().get("/ws", websocket_echo);
+        let server = Server::new("127.0.0.1:0".to_string(), router)
+            .with_upgrade_timeout(Duration::from_millis(100))
+            .without_access_log();
+        let shutdown = server.shutdown_handle();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let running = thread::spawn(move || server.serve(listener));
+
+        // 🤐 handshake แล้วเงียบ: server ต้องปิดเองหลัง timeout ไม่ค้าง worker ไว้
+        let mut stream = connect(addr);
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        assert!(response.starts_with(b"HTTP/1.1 101 Switching Protocols\r\n"));
+
+        shutdown.shutdown();
+        assert!(running.join().unwrap().is_ok());
+    }
+
+    /// 🤝 ต่อ TCP แล้วส่ง handshake (response 101 อ่านพร้อม frame ทีหลัง)
+    fn connect(addr: std::net::SocketAddr) -> TcpStream {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream
+            .write_all(b"GET /ws HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n")
+            .unwrap();
+        stream
+    }
+
+    fn websocket_echo(request: &Request) -> Response {
+        upgrade(request, |mut socket| {
+            while let Ok(message) = socket.read() {
+                match message {
+                    Message::Text(_) | Message::Binary(_) => socket.send(message).unwrap(),
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+        })
+    }
+}
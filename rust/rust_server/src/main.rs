@@ -1,17 +1,124 @@
-use rust_server::http::Server; // 🌐 นำเข้า HTTP Server module
+use chrono::FixedOffset;
+#[cfg(feature = "tls")]
+use rust_server::http::TlsConfig;
+use rust_server::http::{
+    AccessLog, CatchPanic, Compression, Cookie, HttpStatus, LogFormat, RequestId, Response, Router,
+    SameSite, Server, StaticFiles, Timing,
+    sse::{self, Event},
+    websocket::{self, Message},
+}; // 🌐 นำเข้า HTTP Server module
 
 /// 🚀 Entry point: จุดเริ่มต้นของ HTTP Server application
 /// สร้าง TCP server ที่ listen บน localhost port 8000
 fn main() {
-    // 🏠 สร้าง server instance ด้วย IP และ port ที่กำหนด
-    let server = Server::new("127.0.0.1:8000".to_owned());
+    // 🗺️ ลงทะเบียน routes: method + path pattern -> handler
+    let router = Router::new()
+        // 🏠 Home page
+        .get("/", |_| {
+            Response::new(HttpStatus::Ok, Some("home".to_string()))
+        })
+        // 👋 Hello page
+        .get("/hello", |_| {
+            Response::new(HttpStatus::Ok, Some("hello".to_string()))
+        })
+        .get("/hello/:name", |req| {
+            // 🧷 อ่าน path parameter จาก `/hello/:name`
+            let name = req.param("name").unwrap_or_default();
+            Response::new(HttpStatus::Ok, Some(format!("hello {}", name)))
+        })
+        // 🍪 นับจำนวนครั้งที่ browser นี้เข้ามาด้วย cookie
+        .get("/visits", |req| {
+            let visits = req
+                .cookie("visits")
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0)
+                + 1;
+            let cookie = Cookie::new("visits", visits.to_string())
+                .with_path("/")
+                .with_http_only(true)
+                .with_same_site(SameSite::Lax);
+            Response::new(HttpStatus::Ok, Some(format!("visits: {}", visits))).with_cookie(&cookie)
+        })
+        // 🔌 WebSocket echo: ส่ง text/binary กลับไปเหมือนเดิมจนกว่า client จะปิด
+        .get("/ws", |req| {
+            websocket::upgrade(req, |mut socket| {
+                while let Ok(message) = socket.read() {
+                    let echo = match message {
+                        Message::Text(_) | Message::Binary(_) => message,
+                        Message::Close(_) => break,
+                        _ => continue, // 🏓 ping ถูกตอบ pong ให้แล้ว
+                    };
+                    if socket.send(echo).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        // 📡 Server-Sent Events: นับ 1 ถึง 10 ทีละวินาที (ต่อจาก `Last-Event-ID` ได้)
+        .get("/events", |req| {
+            sse::stream(req, |events| {
+                let start = events
+                    .last_event_id()
+                    .and_then(|id| id.parse::<u32>().ok())
+                    .unwrap_or(0);
+                for count in start + 1..=10 {
+                    let event = Event::new(count.to_string())
+                        .event("count")
+                        .id(count.to_string());
+                    if events.send(&event).is_err() {
+                        break; // 🔌 client ปิดไปแล้ว
+                    }
+                    std::thread::sleep(std::time::Duration::from_secs(1));
+                }
+            })
+        })
+        // 📁 path อื่นๆ ส่งไฟล์จาก `public/` (ลงทะเบียนท้ายสุด route ข้างบนจึงถูกเลือกก่อน)
+        .get("/*path", {
+            let files = StaticFiles::default();
+            move |req| files.serve(req.param("path").unwrap_or_default())
+        });
+
+    // 🏠 สร้าง server instance ด้วย IP, port และ router ที่กำหนด
+    // 📜 access log แบบ combined ด้วยเวลาไทย (+07:00)
+    let access_log = AccessLog::new(LogFormat::Combined)
+        .with_timezone(FixedOffset::east_opt(7 * 3600).expect("valid offset"));
+
+    // 🗜️ บีบอัด text response ตาม `Accept-Encoding` ของ client
+    // 🧅 middleware: กัน panic เป็น 500, ใส่ `X-Request-Id` และเวลาที่ใช้ใน response
+    let server = Server::new("127.0.0.1:8000".to_owned(), router)
+        .with_compression(Compression::default())
+        .with_access_log(access_log)
+        .with_middleware(CatchPanic)
+        .with_middleware(RequestId::default())
+        .with_middleware(Timing);
+
+    // 🔐 HTTPS เมื่อเปิด feature `tls` และกำหนดไฟล์ PEM ใน `TLS_CERT` / `TLS_KEY`
+    #[cfg(feature = "tls")]
+    let server = match (std::env::var("TLS_CERT"), std::env::var("TLS_KEY")) {
+        (Ok(cert), Ok(key)) => match TlsConfig::from_pem_files(cert, key) {
+            Ok(tls) => server.with_tls(tls),
+            Err(e) => {
+                eprintln!("❌ Failed to load TLS certificate: {}", e);
+                return;
+            }
+        },
+        _ => server,
+    };
+
+    // 🛑 Ctrl+C (SIGINT) / SIGTERM: หยุดรับ connection ใหม่และรอ request ที่ค้างอยู่ให้เสร็จ
+    let shutdown = server.shutdown_handle();
+    if let Err(e) = ctrlc::set_handler(move || {
+        shutdown.shutdown();
+    }) {
+        eprintln!("⚠️ Failed to install signal handler: {}", e);
+    }
 
     // 💯 เริ่มรัน server และ handle errors
     // ใช้ pattern matching กับ Result type
     if let Err(e) = server.run() {
         eprintln!("❌ Error running server: {}", e); // ส่ง error ไป stderr
     }
-    
-    // 📝 หมายเหตุ: server.run() จะ block thread จนกว่าจะมี error หรือ interrupt
+
+    // 📝 หมายเหตุ: server.run() จะ block thread จนกว่าจะมี error หรือถูกสั่ง shutdown
     // สามารถ test ได้ด้วย: curl http://127.0.0.1:8000/
 }